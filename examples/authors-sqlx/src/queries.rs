@@ -1,83 +1,110 @@
-/// This file is @generated by sqlc-gen-rust.
+//! This file is @generated by sqlc-gen-rust.
 #[derive(Debug, Clone, sqlx::FromRow)]
-pub struct GetAuthorRow {
+pub struct Author {
     pub id: i64,
     pub name: String,
     pub bio: Option<String>,
 }
-pub async fn get_author<'e, E>(db: E, id: i64) -> Result<GetAuthorRow, sqlx::Error>
+/// SQL of [`get_author`].
+pub const GET_AUTHOR: &str = "
+SELECT id, name, bio FROM authors
+WHERE id = $1 LIMIT 1
+";
+/// Generated from `query.sql` (`:one`).
+pub async fn get_author<'e, E>(db: E, id: i64) -> Result<Author, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec: GetAuthorRow = sqlx::query_as(
-            "
-SELECT id, name, bio FROM authors
-WHERE id = $1 LIMIT 1
-",
-        )
-        .bind(id)
-        .fetch_one(db)
-        .await?;
+    let rec: Author = sqlx::query_as(GET_AUTHOR).bind(id).fetch_one(db).await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct ListAuthorsRow {
-    pub id: i64,
-    pub name: String,
-    pub bio: Option<String>,
-}
-pub async fn list_authors<'e, E>(db: E) -> Result<Vec<ListAuthorsRow>, sqlx::Error>
+/// Generated from `query.sql` (`:one`).
+pub async fn get_author_optional<'e, E>(
+    db: E,
+    id: i64,
+) -> Result<Option<Author>, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec: Vec<ListAuthorsRow> = sqlx::query_as(
-            "
-SELECT id, name, bio FROM authors
-ORDER BY name
-",
-        )
-        .fetch_all(db)
+    let rec: Option<Author> = sqlx::query_as(GET_AUTHOR)
+        .bind(id)
+        .fetch_optional(db)
         .await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct CreateAuthorRow {
-    pub id: i64,
-    pub name: String,
-    pub bio: Option<String>,
+/// SQL of [`list_authors`].
+pub const LIST_AUTHORS: &str = "
+SELECT id, name, bio FROM authors
+ORDER BY name
+";
+/// Generated from `query.sql` (`:many`).
+pub async fn list_authors<'e, E>(db: E) -> Result<Vec<Author>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: Vec<Author> = sqlx::query_as(LIST_AUTHORS).fetch_all(db).await?;
+    Ok(rec)
 }
-pub async fn create_author<'e, E>(
+/// Generated from `query.sql` (`:many`).
+pub fn list_authors_stream<'e, E>(
     db: E,
-    name: String,
-    bio: Option<String>,
-) -> Result<CreateAuthorRow, sqlx::Error>
+) -> impl futures::Stream<Item = Result<Author, sqlx::Error>> + 'e
 where
-    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+    E: sqlx::Executor<'e, Database = sqlx::Postgres> + 'e,
 {
-    let rec: CreateAuthorRow = sqlx::query_as(
-            "
+    sqlx::query_as(LIST_AUTHORS).fetch(db)
+}
+/// SQL of [`create_author`].
+pub const CREATE_AUTHOR: &str = "
 INSERT INTO authors (
   name, bio
 ) VALUES (
   $1, $2
 )
 RETURNING id, name, bio
-",
-        )
+";
+/// Generated from `query.sql` (`:one`).
+pub async fn create_author<'e, E>(
+    db: E,
+    name: String,
+    bio: Option<String>,
+) -> Result<Author, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: Author = sqlx::query_as(CREATE_AUTHOR)
         .bind(name)
         .bind(bio)
         .fetch_one(db)
         .await?;
     Ok(rec)
 }
-pub async fn delete_author<'e, E>(db: E, id: i64) -> Result<(), sqlx::Error>
+/// Generated from `query.sql` (`:one`).
+pub async fn create_author_optional<'e, E>(
+    db: E,
+    name: String,
+    bio: Option<String>,
+) -> Result<Option<Author>, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    sqlx::query("
+    let rec: Option<Author> = sqlx::query_as(CREATE_AUTHOR)
+        .bind(name)
+        .bind(bio)
+        .fetch_optional(db)
+        .await?;
+    Ok(rec)
+}
+/// SQL of [`delete_author`].
+pub const DELETE_AUTHOR: &str = "
 DELETE FROM authors
 WHERE id = $1
-"
-    ).bind(id).execute(db).await?;
+";
+/// Generated from `query.sql` (`:exec`).
+pub async fn delete_author<'e, E>(db: E, id: i64) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    sqlx::query(DELETE_AUTHOR).bind(id).execute(db).await?;
     Ok(())
 }
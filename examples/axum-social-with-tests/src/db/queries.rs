@@ -1,4 +1,36 @@
-/// This file is @generated by sqlc-gen-rust.
+//! This file is @generated by sqlc-gen-rust.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct User {
+    pub user_id: uuid::Uuid,
+    pub username: String,
+    pub password_hash: String,
+}
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Post {
+    pub post_id: uuid::Uuid,
+    pub user_id: uuid::Uuid,
+    pub content: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Comment {
+    pub comment_id: uuid::Uuid,
+    pub post_id: uuid::Uuid,
+    pub user_id: uuid::Uuid,
+    pub content: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+/// SQL of [`create_post`].
+pub const CREATE_POST: &str = "
+with inserted_post as (
+    insert into post(user_id, content)
+    values ($1, $2)
+    returning post_id, user_id, content, created_at
+)
+select post_id, username, content, created_at
+from inserted_post
+inner join \"user\" using (user_id)
+";
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct CreatePostRow {
     pub post_id: uuid::Uuid,
@@ -6,6 +38,7 @@ pub struct CreatePostRow {
     pub content: String,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
+/// Generated from `queries.sql` (`:one`).
 pub async fn create_post<'e, E>(
     db: E,
     user_id: uuid::Uuid,
@@ -14,24 +47,36 @@ pub async fn create_post<'e, E>(
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec: CreatePostRow = sqlx::query_as(
-            "
-with inserted_post as (
-    insert into post(user_id, content)
-    values ($1, $2)
-    returning post_id, user_id, content, created_at
-)
-select post_id, username, content, created_at
-from inserted_post
-inner join \"user\" using (user_id)
-",
-        )
+    let rec: CreatePostRow = sqlx::query_as(CREATE_POST)
         .bind(user_id)
         .bind(content)
         .fetch_one(db)
         .await?;
     Ok(rec)
 }
+/// Generated from `queries.sql` (`:one`).
+pub async fn create_post_optional<'e, E>(
+    db: E,
+    user_id: uuid::Uuid,
+    content: String,
+) -> Result<Option<CreatePostRow>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: Option<CreatePostRow> = sqlx::query_as(CREATE_POST)
+        .bind(user_id)
+        .bind(content)
+        .fetch_optional(db)
+        .await?;
+    Ok(rec)
+}
+/// SQL of [`get_posts`].
+pub const GET_POSTS: &str = "
+select post_id, username, content, created_at
+from post
+inner join \"user\" using (user_id)
+order by created_at desc
+";
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct GetPostsRow {
     pub post_id: uuid::Uuid,
@@ -39,22 +84,34 @@ pub struct GetPostsRow {
     pub content: String,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
+/// Generated from `queries.sql` (`:many`).
 pub async fn get_posts<'e, E>(db: E) -> Result<Vec<GetPostsRow>, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec: Vec<GetPostsRow> = sqlx::query_as(
-            "
-select post_id, username, content, created_at
-from post
-inner join \"user\" using (user_id)
-order by created_at desc
-",
-        )
-        .fetch_all(db)
-        .await?;
+    let rec: Vec<GetPostsRow> = sqlx::query_as(GET_POSTS).fetch_all(db).await?;
     Ok(rec)
 }
+/// Generated from `queries.sql` (`:many`).
+pub fn get_posts_stream<'e, E>(
+    db: E,
+) -> impl futures::Stream<Item = Result<GetPostsRow, sqlx::Error>> + 'e
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres> + 'e,
+{
+    sqlx::query_as(GET_POSTS).fetch(db)
+}
+/// SQL of [`create_comment`].
+pub const CREATE_COMMENT: &str = "
+with inserted_comment as (
+    insert into comment(user_id, post_id, content)
+    values ($1, $2, $3)
+    returning comment_id, user_id, content, created_at
+)
+select comment_id, username, content, created_at
+from inserted_comment
+inner join \"user\" using (user_id)
+";
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct CreateCommentInfo {
     pub user_id: uuid::Uuid,
@@ -68,6 +125,7 @@ pub struct CreateCommentRow {
     pub content: String,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
+/// Generated from `queries.sql` (`:one`).
 pub async fn create_comment<'e, E>(
     db: E,
     create_comment_info: CreateCommentInfo,
@@ -75,18 +133,7 @@ pub async fn create_comment<'e, E>(
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec: CreateCommentRow = sqlx::query_as(
-            "
-with inserted_comment as (
-    insert into comment(user_id, post_id, content)
-    values ($1, $2, $3)
-    returning comment_id, user_id, content, created_at
-)
-select comment_id, username, content, created_at
-from inserted_comment
-inner join \"user\" using (user_id)
-",
-        )
+    let rec: CreateCommentRow = sqlx::query_as(CREATE_COMMENT)
         .bind(create_comment_info.user_id)
         .bind(create_comment_info.post_id)
         .bind(create_comment_info.content)
@@ -94,6 +141,30 @@ inner join \"user\" using (user_id)
         .await?;
     Ok(rec)
 }
+/// Generated from `queries.sql` (`:one`).
+pub async fn create_comment_optional<'e, E>(
+    db: E,
+    create_comment_info: CreateCommentInfo,
+) -> Result<Option<CreateCommentRow>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: Option<CreateCommentRow> = sqlx::query_as(CREATE_COMMENT)
+        .bind(create_comment_info.user_id)
+        .bind(create_comment_info.post_id)
+        .bind(create_comment_info.content)
+        .fetch_optional(db)
+        .await?;
+    Ok(rec)
+}
+/// SQL of [`get_comments`].
+pub const GET_COMMENTS: &str = "
+select comment_id, username, content, created_at
+from comment
+inner join \"user\" using (user_id)
+where post_id = $1
+order by created_at
+";
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct GetCommentsRow {
     pub comment_id: uuid::Uuid,
@@ -101,6 +172,7 @@ pub struct GetCommentsRow {
     pub content: String,
     pub created_at: chrono::DateTime<chrono::Utc>,
 }
+/// Generated from `queries.sql` (`:many`).
 pub async fn get_comments<'e, E>(
     db: E,
     post_id: uuid::Uuid,
@@ -108,20 +180,28 @@ pub async fn get_comments<'e, E>(
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec: Vec<GetCommentsRow> = sqlx::query_as(
-            "
-select comment_id, username, content, created_at
-from comment
-inner join \"user\" using (user_id)
-where post_id = $1
-order by created_at
-",
-        )
+    let rec: Vec<GetCommentsRow> = sqlx::query_as(GET_COMMENTS)
         .bind(post_id)
         .fetch_all(db)
         .await?;
     Ok(rec)
 }
+/// Generated from `queries.sql` (`:many`).
+pub fn get_comments_stream<'e, E>(
+    db: E,
+    post_id: uuid::Uuid,
+) -> impl futures::Stream<Item = Result<GetCommentsRow, sqlx::Error>> + 'e
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres> + 'e,
+{
+    sqlx::query_as(GET_COMMENTS).bind(post_id).fetch(db)
+}
+/// SQL of [`create_user`].
+pub const CREATE_USER: &str = "
+insert into \"user\"(username, password_hash)
+values ($1, $2)
+";
+/// Generated from `queries.sql` (`:exec`).
 pub async fn create_user<'e, E>(
     db: E,
     username: String,
@@ -130,22 +210,19 @@ pub async fn create_user<'e, E>(
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    sqlx::query("
-insert into \"user\"(username, password_hash)
-values ($1, $2)
-"
-    )
-        .bind(username)
-        .bind(password_hash)
-        .execute(db)
-        .await?;
+    sqlx::query(CREATE_USER).bind(username).bind(password_hash).execute(db).await?;
     Ok(())
 }
+/// SQL of [`get_user_by_username`].
+pub const GET_USER_BY_USERNAME: &str = "
+select user_id, password_hash from \"user\" where username = $1
+";
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct GetUserByUsernameRow {
     pub user_id: uuid::Uuid,
     pub password_hash: String,
 }
+/// Generated from `queries.sql` (`:one`).
 pub async fn get_user_by_username<'e, E>(
     db: E,
     username: String,
@@ -153,13 +230,23 @@ pub async fn get_user_by_username<'e, E>(
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec: GetUserByUsernameRow = sqlx::query_as(
-            "
-select user_id, password_hash from \"user\" where username = $1
-",
-        )
+    let rec: GetUserByUsernameRow = sqlx::query_as(GET_USER_BY_USERNAME)
         .bind(username)
         .fetch_one(db)
         .await?;
     Ok(rec)
 }
+/// Generated from `queries.sql` (`:one`).
+pub async fn get_user_by_username_optional<'e, E>(
+    db: E,
+    username: String,
+) -> Result<Option<GetUserByUsernameRow>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: Option<GetUserByUsernameRow> = sqlx::query_as(GET_USER_BY_USERNAME)
+        .bind(username)
+        .fetch_optional(db)
+        .await?;
+    Ok(rec)
+}
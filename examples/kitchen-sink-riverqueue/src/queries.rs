@@ -1,24 +1,91 @@
-/// This file is @generated by sqlc-gen-rust.
+//! This file is @generated by sqlc-gen-rust.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, sqlx::Type)]
+#[sqlx(type_name = "river_job_state", rename_all = "lowercase")]
+pub enum RiverJobState {
+    Available,
+    Cancelled,
+    Completed,
+    Discarded,
+    Pending,
+    Retryable,
+    Running,
+    Scheduled,
+}
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct RiverClient {
+    pub id: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub metadata: sqlx::types::Json<serde_json::Value>,
+    pub paused_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct RiverClientQueue {
+    pub river_client_id: String,
+    pub name: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub max_workers: i64,
+    pub metadata: sqlx::types::Json<serde_json::Value>,
+    pub num_jobs_completed: i64,
+    pub num_jobs_running: i64,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct RiverJob {
+    pub id: i64,
+    pub args: sqlx::types::Json<serde_json::Value>,
+    pub attempt: i16,
+    pub attempted_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub attempted_by: Option<Vec<String>>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub errors: Option<Vec<sqlx::types::Json<serde_json::Value>>>,
+    pub finalized_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub kind: String,
+    pub max_attempts: i16,
+    pub metadata: sqlx::types::Json<serde_json::Value>,
+    pub priority: i16,
+    pub queue: String,
+    pub state: RiverJobState,
+    pub scheduled_at: chrono::DateTime<chrono::Utc>,
+    pub tags: Vec<String>,
+    pub unique_key: Option<Vec<u8>>,
+    pub unique_states: Option<bit_vec::BitVec>,
+}
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct RiverLeader {
+    pub elected_at: chrono::DateTime<chrono::Utc>,
+    pub expires_at: chrono::DateTime<chrono::Utc>,
+    pub leader_id: String,
+    pub name: String,
+}
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct RiverMigration {
+    pub line: String,
+    pub version: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct RiverQueue {
+    pub name: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub metadata: sqlx::types::Json<serde_json::Value>,
+    pub paused_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+/// SQL of [`pg_advisory_xact_lock`].
+pub const PG_ADVISORY_XACT_LOCK: &str = "
+SELECT pg_advisory_xact_lock($1)
+";
+/// Generated from `pg_misc.sql` (`:exec`).
 pub async fn pg_advisory_xact_lock<'e, E>(db: E, key: i64) -> Result<(), sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    sqlx::query("
-SELECT pg_advisory_xact_lock($1)
-"
-    ).bind(key).execute(db).await?;
+    sqlx::query(PG_ADVISORY_XACT_LOCK).bind(key).execute(db).await?;
     Ok(())
 }
-pub async fn pg_notify_many<'e, E>(
-    db: E,
-    topic: String,
-    payload: Vec<String>,
-) -> Result<(), sqlx::Error>
-where
-    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
-{
-    sqlx::query(
-            "
+/// SQL of [`pg_notify_many`].
+pub const PG_NOTIFY_MANY: &str = "
 WITH topic_to_notify AS (
     SELECT
         concat(current_schema(), '.', $1::text) AS topic,
@@ -29,38 +96,21 @@ SELECT pg_notify(
     topic_to_notify.payload
   )
 FROM topic_to_notify
-",
-        )
-        .bind(topic)
-        .bind(payload)
-        .execute(db)
-        .await?;
-    Ok(())
-}
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct ClientCreateOrSetUpdatedAtInfo {
-    pub id: String,
-    pub metadata: sqlx::types::Json<serde_json::Value>,
-    pub paused_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
-}
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct ClientCreateOrSetUpdatedAtRow {
-    pub id: String,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub metadata: sqlx::types::Json<serde_json::Value>,
-    pub paused_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub updated_at: chrono::DateTime<chrono::Utc>,
-}
-pub async fn client_create_or_set_updated_at<'e, E>(
+";
+/// Generated from `pg_misc.sql` (`:exec`).
+pub async fn pg_notify_many<'e, E>(
     db: E,
-    client_create_or_set_updated_at_info: ClientCreateOrSetUpdatedAtInfo,
-) -> Result<ClientCreateOrSetUpdatedAtRow, sqlx::Error>
+    topic: String,
+    payload: Vec<String>,
+) -> Result<(), sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec: ClientCreateOrSetUpdatedAtRow = sqlx::query_as(
-            "
+    sqlx::query(PG_NOTIFY_MANY).bind(topic).bind(payload).execute(db).await?;
+    Ok(())
+}
+/// SQL of [`client_create_or_set_updated_at`].
+pub const CLIENT_CREATE_OR_SET_UPDATED_AT: &str = "
 INSERT INTO river_client (
     id,
     metadata,
@@ -75,8 +125,23 @@ INSERT INTO river_client (
 SET
     updated_at = coalesce($4::timestamptz, now())
 RETURNING id, created_at, metadata, paused_at, updated_at
-",
-        )
+";
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ClientCreateOrSetUpdatedAtInfo {
+    pub id: String,
+    pub metadata: sqlx::types::Json<serde_json::Value>,
+    pub paused_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+/// Generated from `river_client.sql` (`:one`).
+pub async fn client_create_or_set_updated_at<'e, E>(
+    db: E,
+    client_create_or_set_updated_at_info: ClientCreateOrSetUpdatedAtInfo,
+) -> Result<RiverClient, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: RiverClient = sqlx::query_as(CLIENT_CREATE_OR_SET_UPDATED_AT)
         .bind(client_create_or_set_updated_at_info.id)
         .bind(client_create_or_set_updated_at_info.metadata)
         .bind(client_create_or_set_updated_at_info.paused_at)
@@ -85,34 +150,25 @@ RETURNING id, created_at, metadata, paused_at, updated_at
         .await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct ClientQueueCreateOrSetUpdatedAtManyInfo {
-    pub metadata: sqlx::types::Json<serde_json::Value>,
-    pub name: Vec<String>,
-    pub paused_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub river_client_id: String,
-    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
-}
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct ClientQueueCreateOrSetUpdatedAtManyRow {
-    pub river_client_id: String,
-    pub name: String,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub max_workers: i64,
-    pub metadata: sqlx::types::Json<serde_json::Value>,
-    pub num_jobs_completed: i64,
-    pub num_jobs_running: i64,
-    pub updated_at: chrono::DateTime<chrono::Utc>,
-}
-pub async fn client_queue_create_or_set_updated_at_many<'e, E>(
+/// Generated from `river_client.sql` (`:one`).
+pub async fn client_create_or_set_updated_at_optional<'e, E>(
     db: E,
-    client_queue_create_or_set_updated_at_many_info: ClientQueueCreateOrSetUpdatedAtManyInfo,
-) -> Result<ClientQueueCreateOrSetUpdatedAtManyRow, sqlx::Error>
+    client_create_or_set_updated_at_info: ClientCreateOrSetUpdatedAtInfo,
+) -> Result<Option<RiverClient>, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec: ClientQueueCreateOrSetUpdatedAtManyRow = sqlx::query_as(
-            "
+    let rec: Option<RiverClient> = sqlx::query_as(CLIENT_CREATE_OR_SET_UPDATED_AT)
+        .bind(client_create_or_set_updated_at_info.id)
+        .bind(client_create_or_set_updated_at_info.metadata)
+        .bind(client_create_or_set_updated_at_info.paused_at)
+        .bind(client_create_or_set_updated_at_info.updated_at)
+        .fetch_optional(db)
+        .await?;
+    Ok(rec)
+}
+/// SQL of [`client_queue_create_or_set_updated_at_many`].
+pub const CLIENT_QUEUE_CREATE_OR_SET_UPDATED_AT_MANY: &str = "
 INSERT INTO river_client_queue (
     metadata,
     name,
@@ -129,7 +185,25 @@ INSERT INTO river_client_queue (
 SET
     updated_at = coalesce($5::timestamptz, now())
 RETURNING river_client_id, name, created_at, max_workers, metadata, num_jobs_completed, num_jobs_running, updated_at
-",
+";
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ClientQueueCreateOrSetUpdatedAtManyInfo {
+    pub metadata: sqlx::types::Json<serde_json::Value>,
+    pub name: Vec<String>,
+    pub paused_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub river_client_id: String,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+/// Generated from `river_client_queue.sql` (`:one`).
+pub async fn client_queue_create_or_set_updated_at_many<'e, E>(
+    db: E,
+    client_queue_create_or_set_updated_at_many_info: ClientQueueCreateOrSetUpdatedAtManyInfo,
+) -> Result<RiverClientQueue, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: RiverClientQueue = sqlx::query_as(
+            CLIENT_QUEUE_CREATE_OR_SET_UPDATED_AT_MANY,
         )
         .bind(client_queue_create_or_set_updated_at_many_info.metadata)
         .bind(client_queue_create_or_set_updated_at_many_info.name)
@@ -140,42 +214,28 @@ RETURNING river_client_id, name, created_at, max_workers, metadata, num_jobs_com
         .await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct JobCancelInfo {
-    pub id: i64,
-    pub control_topic: String,
-    pub cancel_attempted_at: sqlx::types::Json<serde_json::Value>,
-}
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct JobCancelRow {
-    pub id: i64,
-    pub args: sqlx::types::Json<serde_json::Value>,
-    pub attempt: i16,
-    pub attempted_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub attempted_by: Option<Vec<String>>,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub errors: Option<Vec<sqlx::types::Json<serde_json::Value>>>,
-    pub finalized_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub kind: String,
-    pub max_attempts: i16,
-    pub metadata: sqlx::types::Json<serde_json::Value>,
-    pub priority: i16,
-    pub queue: String,
-    pub state: sqlx::types::Json<serde_json::Value>,
-    pub scheduled_at: chrono::DateTime<chrono::Utc>,
-    pub tags: Vec<sqlx::types::Json<serde_json::Value>>,
-    pub unique_key: Option<Vec<u8>>,
-    pub unique_states: Option<sqlx::types::Json<serde_json::Value>>,
-}
-pub async fn job_cancel<'e, E>(
+/// Generated from `river_client_queue.sql` (`:one`).
+pub async fn client_queue_create_or_set_updated_at_many_optional<'e, E>(
     db: E,
-    job_cancel_info: JobCancelInfo,
-) -> Result<JobCancelRow, sqlx::Error>
+    client_queue_create_or_set_updated_at_many_info: ClientQueueCreateOrSetUpdatedAtManyInfo,
+) -> Result<Option<RiverClientQueue>, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec: JobCancelRow = sqlx::query_as(
-            "
+    let rec: Option<RiverClientQueue> = sqlx::query_as(
+            CLIENT_QUEUE_CREATE_OR_SET_UPDATED_AT_MANY,
+        )
+        .bind(client_queue_create_or_set_updated_at_many_info.metadata)
+        .bind(client_queue_create_or_set_updated_at_many_info.name)
+        .bind(client_queue_create_or_set_updated_at_many_info.paused_at)
+        .bind(client_queue_create_or_set_updated_at_many_info.river_client_id)
+        .bind(client_queue_create_or_set_updated_at_many_info.updated_at)
+        .fetch_optional(db)
+        .await?;
+    Ok(rec)
+}
+/// SQL of [`job_cancel`].
+pub const JOB_CANCEL: &str = "
 WITH locked_job AS (
     SELECT
         id, queue, state, finalized_at
@@ -217,8 +277,22 @@ WHERE id = $1::bigint
 UNION
 SELECT id, args, attempt, attempted_at, attempted_by, created_at, errors, finalized_at, kind, max_attempts, metadata, priority, queue, state, scheduled_at, tags, unique_key, unique_states
 FROM updated_job
-",
-        )
+";
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct JobCancelInfo {
+    pub id: i64,
+    pub control_topic: String,
+    pub cancel_attempted_at: sqlx::types::Json<serde_json::Value>,
+}
+/// Generated from `river_job.sql` (`:one`).
+pub async fn job_cancel<'e, E>(
+    db: E,
+    job_cancel_info: JobCancelInfo,
+) -> Result<RiverJob, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: RiverJob = sqlx::query_as(JOB_CANCEL)
         .bind(job_cancel_info.id)
         .bind(job_cancel_info.control_topic)
         .bind(job_cancel_info.cancel_attempted_at)
@@ -226,52 +300,58 @@ FROM updated_job
         .await?;
     Ok(rec)
 }
-pub async fn job_count_by_state<'e, E>(
+/// Generated from `river_job.sql` (`:one`).
+pub async fn job_cancel_optional<'e, E>(
     db: E,
-    state: sqlx::types::Json<serde_json::Value>,
-) -> Result<i64, sqlx::Error>
+    job_cancel_info: JobCancelInfo,
+) -> Result<Option<RiverJob>, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec: i64 = sqlx::query_scalar(
-            "
+    let rec: Option<RiverJob> = sqlx::query_as(JOB_CANCEL)
+        .bind(job_cancel_info.id)
+        .bind(job_cancel_info.control_topic)
+        .bind(job_cancel_info.cancel_attempted_at)
+        .fetch_optional(db)
+        .await?;
+    Ok(rec)
+}
+/// SQL of [`job_count_by_state`].
+pub const JOB_COUNT_BY_STATE: &str = "
 SELECT count(*)
 FROM river_job
 WHERE state = $1
-",
-        )
+";
+/// Generated from `river_job.sql` (`:one`).
+pub async fn job_count_by_state<'e, E>(
+    db: E,
+    state: RiverJobState,
+) -> Result<i64, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: i64 = sqlx::query_scalar(JOB_COUNT_BY_STATE)
         .bind(state)
         .fetch_one(db)
         .await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct JobDeleteRow {
-    pub id: i64,
-    pub args: sqlx::types::Json<serde_json::Value>,
-    pub attempt: i16,
-    pub attempted_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub attempted_by: Option<Vec<String>>,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub errors: Option<Vec<sqlx::types::Json<serde_json::Value>>>,
-    pub finalized_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub kind: String,
-    pub max_attempts: i16,
-    pub metadata: sqlx::types::Json<serde_json::Value>,
-    pub priority: i16,
-    pub queue: String,
-    pub state: sqlx::types::Json<serde_json::Value>,
-    pub scheduled_at: chrono::DateTime<chrono::Utc>,
-    pub tags: Vec<sqlx::types::Json<serde_json::Value>>,
-    pub unique_key: Option<Vec<u8>>,
-    pub unique_states: Option<sqlx::types::Json<serde_json::Value>>,
-}
-pub async fn job_delete<'e, E>(db: E, id: i64) -> Result<JobDeleteRow, sqlx::Error>
+/// Generated from `river_job.sql` (`:one`).
+pub async fn job_count_by_state_optional<'e, E>(
+    db: E,
+    state: RiverJobState,
+) -> Result<Option<i64>, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec: JobDeleteRow = sqlx::query_as(
-            "
+    let rec: Option<i64> = sqlx::query_scalar(JOB_COUNT_BY_STATE)
+        .bind(state)
+        .fetch_optional(db)
+        .await?;
+    Ok(rec)
+}
+/// SQL of [`job_delete`].
+pub const JOB_DELETE: &str = "
 WITH job_to_delete AS (
     SELECT id
     FROM river_job
@@ -294,29 +374,31 @@ WHERE id = $1::bigint
 UNION
 SELECT id, args, attempt, attempted_at, attempted_by, created_at, errors, finalized_at, kind, max_attempts, metadata, priority, queue, state, scheduled_at, tags, unique_key, unique_states
 FROM deleted_job
-",
-        )
-        .bind(id)
-        .fetch_one(db)
-        .await?;
+";
+/// Generated from `river_job.sql` (`:one`).
+pub async fn job_delete<'e, E>(db: E, id: i64) -> Result<RiverJob, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: RiverJob = sqlx::query_as(JOB_DELETE).bind(id).fetch_one(db).await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct JobDeleteBeforeInfo {
-    pub cancelled_finalized_at_horizon: chrono::DateTime<chrono::Utc>,
-    pub completed_finalized_at_horizon: chrono::DateTime<chrono::Utc>,
-    pub discarded_finalized_at_horizon: chrono::DateTime<chrono::Utc>,
-    pub max: i64,
-}
-pub async fn job_delete_before<'e, E>(
+/// Generated from `river_job.sql` (`:one`).
+pub async fn job_delete_optional<'e, E>(
     db: E,
-    job_delete_before_info: JobDeleteBeforeInfo,
-) -> Result<i64, sqlx::Error>
+    id: i64,
+) -> Result<Option<RiverJob>, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec: i64 = sqlx::query_scalar(
-            "
+    let rec: Option<RiverJob> = sqlx::query_as(JOB_DELETE)
+        .bind(id)
+        .fetch_optional(db)
+        .await?;
+    Ok(rec)
+}
+/// SQL of [`job_delete_before`].
+pub const JOB_DELETE_BEFORE: &str = "
 WITH deleted_jobs AS (
     DELETE FROM river_job
     WHERE id IN (
@@ -333,9 +415,24 @@ WITH deleted_jobs AS (
 )
 SELECT count(*)
 FROM deleted_jobs
-",
-        )
-        .bind(job_delete_before_info.cancelled_finalized_at_horizon)
+";
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct JobDeleteBeforeInfo {
+    pub cancelled_finalized_at_horizon: chrono::DateTime<chrono::Utc>,
+    pub completed_finalized_at_horizon: chrono::DateTime<chrono::Utc>,
+    pub discarded_finalized_at_horizon: chrono::DateTime<chrono::Utc>,
+    pub max: i64,
+}
+/// Generated from `river_job.sql` (`:one`).
+pub async fn job_delete_before<'e, E>(
+    db: E,
+    job_delete_before_info: JobDeleteBeforeInfo,
+) -> Result<i64, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: i64 = sqlx::query_scalar(JOB_DELETE_BEFORE)
+        .bind(job_delete_before_info.cancelled_finalized_at_horizon)
         .bind(job_delete_before_info.completed_finalized_at_horizon)
         .bind(job_delete_before_info.discarded_finalized_at_horizon)
         .bind(job_delete_before_info.max)
@@ -343,43 +440,25 @@ FROM deleted_jobs
         .await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct JobGetAvailableInfo {
-    pub attempted_by: String,
-    pub queue: String,
-    pub now: Option<chrono::DateTime<chrono::Utc>>,
-    pub max: i32,
-}
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct JobGetAvailableRow {
-    pub id: i64,
-    pub args: sqlx::types::Json<serde_json::Value>,
-    pub attempt: i16,
-    pub attempted_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub attempted_by: Option<Vec<String>>,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub errors: Option<Vec<sqlx::types::Json<serde_json::Value>>>,
-    pub finalized_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub kind: String,
-    pub max_attempts: i16,
-    pub metadata: sqlx::types::Json<serde_json::Value>,
-    pub priority: i16,
-    pub queue: String,
-    pub state: sqlx::types::Json<serde_json::Value>,
-    pub scheduled_at: chrono::DateTime<chrono::Utc>,
-    pub tags: Vec<sqlx::types::Json<serde_json::Value>>,
-    pub unique_key: Option<Vec<u8>>,
-    pub unique_states: Option<sqlx::types::Json<serde_json::Value>>,
-}
-pub async fn job_get_available<'e, E>(
+/// Generated from `river_job.sql` (`:one`).
+pub async fn job_delete_before_optional<'e, E>(
     db: E,
-    job_get_available_info: JobGetAvailableInfo,
-) -> Result<Vec<JobGetAvailableRow>, sqlx::Error>
+    job_delete_before_info: JobDeleteBeforeInfo,
+) -> Result<Option<i64>, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec: Vec<JobGetAvailableRow> = sqlx::query_as(
-            "
+    let rec: Option<i64> = sqlx::query_scalar(JOB_DELETE_BEFORE)
+        .bind(job_delete_before_info.cancelled_finalized_at_horizon)
+        .bind(job_delete_before_info.completed_finalized_at_horizon)
+        .bind(job_delete_before_info.discarded_finalized_at_horizon)
+        .bind(job_delete_before_info.max)
+        .fetch_optional(db)
+        .await?;
+    Ok(rec)
+}
+/// SQL of [`job_get_available`].
+pub const JOB_GET_AVAILABLE: &str = "
 WITH locked_jobs AS (
     SELECT
         id, args, attempt, attempted_at, attempted_by, created_at, errors, finalized_at, kind, max_attempts, metadata, priority, queue, state, scheduled_at, tags, unique_key, unique_states
@@ -410,8 +489,23 @@ WHERE
     river_job.id = locked_jobs.id
 RETURNING
     river_job.id, river_job.args, river_job.attempt, river_job.attempted_at, river_job.attempted_by, river_job.created_at, river_job.errors, river_job.finalized_at, river_job.kind, river_job.max_attempts, river_job.metadata, river_job.priority, river_job.queue, river_job.state, river_job.scheduled_at, river_job.tags, river_job.unique_key, river_job.unique_states
-",
-        )
+";
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct JobGetAvailableInfo {
+    pub attempted_by: String,
+    pub queue: String,
+    pub now: Option<chrono::DateTime<chrono::Utc>>,
+    pub max: i32,
+}
+/// Generated from `river_job.sql` (`:many`).
+pub async fn job_get_available<'e, E>(
+    db: E,
+    job_get_available_info: JobGetAvailableInfo,
+) -> Result<Vec<RiverJob>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: Vec<RiverJob> = sqlx::query_as(JOB_GET_AVAILABLE)
         .bind(job_get_available_info.attempted_by)
         .bind(job_get_available_info.queue)
         .bind(job_get_available_info.now)
@@ -420,6 +514,31 @@ RETURNING
         .await?;
     Ok(rec)
 }
+/// Generated from `river_job.sql` (`:many`).
+pub fn job_get_available_stream<'e, E>(
+    db: E,
+    job_get_available_info: JobGetAvailableInfo,
+) -> impl futures::Stream<Item = Result<RiverJob, sqlx::Error>> + 'e
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres> + 'e,
+{
+    sqlx::query_as(JOB_GET_AVAILABLE)
+        .bind(job_get_available_info.attempted_by)
+        .bind(job_get_available_info.queue)
+        .bind(job_get_available_info.now)
+        .bind(job_get_available_info.max)
+        .fetch(db)
+}
+/// SQL of [`job_get_by_kind_and_unique_properties`].
+pub const JOB_GET_BY_KIND_AND_UNIQUE_PROPERTIES: &str = "
+SELECT id, args, attempt, attempted_at, attempted_by, created_at, errors, finalized_at, kind, max_attempts, metadata, priority, queue, state, scheduled_at, tags, unique_key, unique_states
+FROM river_job
+WHERE kind = $1
+    AND CASE WHEN $2::boolean THEN args = $3 ELSE true END
+    AND CASE WHEN $4::boolean THEN tstzrange($5::timestamptz, $6::timestamptz, '[)') @> created_at ELSE true END
+    AND CASE WHEN $7::boolean THEN queue = $8 ELSE true END
+    AND CASE WHEN $9::boolean THEN state::text = any($10::text[]) ELSE true END
+";
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct JobGetByKindAndUniquePropertiesInfo {
     pub kind: String,
@@ -433,45 +552,15 @@ pub struct JobGetByKindAndUniquePropertiesInfo {
     pub by_state: bool,
     pub state: Vec<String>,
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct JobGetByKindAndUniquePropertiesRow {
-    pub id: i64,
-    pub args: sqlx::types::Json<serde_json::Value>,
-    pub attempt: i16,
-    pub attempted_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub attempted_by: Option<Vec<String>>,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub errors: Option<Vec<sqlx::types::Json<serde_json::Value>>>,
-    pub finalized_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub kind: String,
-    pub max_attempts: i16,
-    pub metadata: sqlx::types::Json<serde_json::Value>,
-    pub priority: i16,
-    pub queue: String,
-    pub state: sqlx::types::Json<serde_json::Value>,
-    pub scheduled_at: chrono::DateTime<chrono::Utc>,
-    pub tags: Vec<sqlx::types::Json<serde_json::Value>>,
-    pub unique_key: Option<Vec<u8>>,
-    pub unique_states: Option<sqlx::types::Json<serde_json::Value>>,
-}
+/// Generated from `river_job.sql` (`:one`).
 pub async fn job_get_by_kind_and_unique_properties<'e, E>(
     db: E,
     job_get_by_kind_and_unique_properties_info: JobGetByKindAndUniquePropertiesInfo,
-) -> Result<JobGetByKindAndUniquePropertiesRow, sqlx::Error>
+) -> Result<RiverJob, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec: JobGetByKindAndUniquePropertiesRow = sqlx::query_as(
-            "
-SELECT id, args, attempt, attempted_at, attempted_by, created_at, errors, finalized_at, kind, max_attempts, metadata, priority, queue, state, scheduled_at, tags, unique_key, unique_states
-FROM river_job
-WHERE kind = $1
-    AND CASE WHEN $2::boolean THEN args = $3 ELSE true END
-    AND CASE WHEN $4::boolean THEN tstzrange($5::timestamptz, $6::timestamptz, '[)') @> created_at ELSE true END
-    AND CASE WHEN $7::boolean THEN queue = $8 ELSE true END
-    AND CASE WHEN $9::boolean THEN state::text = any($10::text[]) ELSE true END
-",
-        )
+    let rec: RiverJob = sqlx::query_as(JOB_GET_BY_KIND_AND_UNIQUE_PROPERTIES)
         .bind(job_get_by_kind_and_unique_properties_info.kind)
         .bind(job_get_by_kind_and_unique_properties_info.by_args)
         .bind(job_get_by_kind_and_unique_properties_info.args)
@@ -486,199 +575,158 @@ WHERE kind = $1
         .await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct JobGetByKindManyRow {
-    pub id: i64,
-    pub args: sqlx::types::Json<serde_json::Value>,
-    pub attempt: i16,
-    pub attempted_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub attempted_by: Option<Vec<String>>,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub errors: Option<Vec<sqlx::types::Json<serde_json::Value>>>,
-    pub finalized_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub kind: String,
-    pub max_attempts: i16,
-    pub metadata: sqlx::types::Json<serde_json::Value>,
-    pub priority: i16,
-    pub queue: String,
-    pub state: sqlx::types::Json<serde_json::Value>,
-    pub scheduled_at: chrono::DateTime<chrono::Utc>,
-    pub tags: Vec<sqlx::types::Json<serde_json::Value>>,
-    pub unique_key: Option<Vec<u8>>,
-    pub unique_states: Option<sqlx::types::Json<serde_json::Value>>,
-}
-pub async fn job_get_by_kind_many<'e, E>(
+/// Generated from `river_job.sql` (`:one`).
+pub async fn job_get_by_kind_and_unique_properties_optional<'e, E>(
     db: E,
-    kind: Vec<String>,
-) -> Result<Vec<JobGetByKindManyRow>, sqlx::Error>
+    job_get_by_kind_and_unique_properties_info: JobGetByKindAndUniquePropertiesInfo,
+) -> Result<Option<RiverJob>, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec: Vec<JobGetByKindManyRow> = sqlx::query_as(
-            "
+    let rec: Option<RiverJob> = sqlx::query_as(JOB_GET_BY_KIND_AND_UNIQUE_PROPERTIES)
+        .bind(job_get_by_kind_and_unique_properties_info.kind)
+        .bind(job_get_by_kind_and_unique_properties_info.by_args)
+        .bind(job_get_by_kind_and_unique_properties_info.args)
+        .bind(job_get_by_kind_and_unique_properties_info.by_created_at)
+        .bind(job_get_by_kind_and_unique_properties_info.created_at_begin)
+        .bind(job_get_by_kind_and_unique_properties_info.created_at_end)
+        .bind(job_get_by_kind_and_unique_properties_info.by_queue)
+        .bind(job_get_by_kind_and_unique_properties_info.queue)
+        .bind(job_get_by_kind_and_unique_properties_info.by_state)
+        .bind(job_get_by_kind_and_unique_properties_info.state)
+        .fetch_optional(db)
+        .await?;
+    Ok(rec)
+}
+/// SQL of [`job_get_by_kind_many`].
+pub const JOB_GET_BY_KIND_MANY: &str = "
 SELECT id, args, attempt, attempted_at, attempted_by, created_at, errors, finalized_at, kind, max_attempts, metadata, priority, queue, state, scheduled_at, tags, unique_key, unique_states
 FROM river_job
 WHERE kind = any($1::text[])
 ORDER BY id
-",
-        )
+";
+/// Generated from `river_job.sql` (`:many`).
+pub async fn job_get_by_kind_many<'e, E>(
+    db: E,
+    kind: Vec<String>,
+) -> Result<Vec<RiverJob>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: Vec<RiverJob> = sqlx::query_as(JOB_GET_BY_KIND_MANY)
         .bind(kind)
         .fetch_all(db)
         .await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct JobGetByIdRow {
-    pub id: i64,
-    pub args: sqlx::types::Json<serde_json::Value>,
-    pub attempt: i16,
-    pub attempted_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub attempted_by: Option<Vec<String>>,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub errors: Option<Vec<sqlx::types::Json<serde_json::Value>>>,
-    pub finalized_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub kind: String,
-    pub max_attempts: i16,
-    pub metadata: sqlx::types::Json<serde_json::Value>,
-    pub priority: i16,
-    pub queue: String,
-    pub state: sqlx::types::Json<serde_json::Value>,
-    pub scheduled_at: chrono::DateTime<chrono::Utc>,
-    pub tags: Vec<sqlx::types::Json<serde_json::Value>>,
-    pub unique_key: Option<Vec<u8>>,
-    pub unique_states: Option<sqlx::types::Json<serde_json::Value>>,
-}
-pub async fn job_get_by_id<'e, E>(db: E, id: i64) -> Result<JobGetByIdRow, sqlx::Error>
+/// Generated from `river_job.sql` (`:many`).
+pub fn job_get_by_kind_many_stream<'e, E>(
+    db: E,
+    kind: Vec<String>,
+) -> impl futures::Stream<Item = Result<RiverJob, sqlx::Error>> + 'e
 where
-    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+    E: sqlx::Executor<'e, Database = sqlx::Postgres> + 'e,
 {
-    let rec: JobGetByIdRow = sqlx::query_as(
-            "
+    sqlx::query_as(JOB_GET_BY_KIND_MANY).bind(kind).fetch(db)
+}
+/// SQL of [`job_get_by_id`].
+pub const JOB_GET_BY_ID: &str = "
 SELECT id, args, attempt, attempted_at, attempted_by, created_at, errors, finalized_at, kind, max_attempts, metadata, priority, queue, state, scheduled_at, tags, unique_key, unique_states
 FROM river_job
 WHERE id = $1
 LIMIT 1
-",
-        )
-        .bind(id)
-        .fetch_one(db)
-        .await?;
+";
+/// Generated from `river_job.sql` (`:one`).
+pub async fn job_get_by_id<'e, E>(db: E, id: i64) -> Result<RiverJob, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: RiverJob = sqlx::query_as(JOB_GET_BY_ID).bind(id).fetch_one(db).await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct JobGetByIdManyRow {
-    pub id: i64,
-    pub args: sqlx::types::Json<serde_json::Value>,
-    pub attempt: i16,
-    pub attempted_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub attempted_by: Option<Vec<String>>,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub errors: Option<Vec<sqlx::types::Json<serde_json::Value>>>,
-    pub finalized_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub kind: String,
-    pub max_attempts: i16,
-    pub metadata: sqlx::types::Json<serde_json::Value>,
-    pub priority: i16,
-    pub queue: String,
-    pub state: sqlx::types::Json<serde_json::Value>,
-    pub scheduled_at: chrono::DateTime<chrono::Utc>,
-    pub tags: Vec<sqlx::types::Json<serde_json::Value>>,
-    pub unique_key: Option<Vec<u8>>,
-    pub unique_states: Option<sqlx::types::Json<serde_json::Value>>,
-}
-pub async fn job_get_by_id_many<'e, E>(
+/// Generated from `river_job.sql` (`:one`).
+pub async fn job_get_by_id_optional<'e, E>(
     db: E,
-    id: Vec<i64>,
-) -> Result<Vec<JobGetByIdManyRow>, sqlx::Error>
+    id: i64,
+) -> Result<Option<RiverJob>, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec: Vec<JobGetByIdManyRow> = sqlx::query_as(
-            "
+    let rec: Option<RiverJob> = sqlx::query_as(JOB_GET_BY_ID)
+        .bind(id)
+        .fetch_optional(db)
+        .await?;
+    Ok(rec)
+}
+/// SQL of [`job_get_by_id_many`].
+pub const JOB_GET_BY_ID_MANY: &str = "
 SELECT id, args, attempt, attempted_at, attempted_by, created_at, errors, finalized_at, kind, max_attempts, metadata, priority, queue, state, scheduled_at, tags, unique_key, unique_states
 FROM river_job
 WHERE id = any($1::bigint[])
 ORDER BY id
-",
-        )
+";
+/// Generated from `river_job.sql` (`:many`).
+pub async fn job_get_by_id_many<'e, E>(
+    db: E,
+    id: Vec<i64>,
+) -> Result<Vec<RiverJob>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: Vec<RiverJob> = sqlx::query_as(JOB_GET_BY_ID_MANY)
         .bind(id)
         .fetch_all(db)
         .await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct JobGetStuckRow {
-    pub id: i64,
-    pub args: sqlx::types::Json<serde_json::Value>,
-    pub attempt: i16,
-    pub attempted_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub attempted_by: Option<Vec<String>>,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub errors: Option<Vec<sqlx::types::Json<serde_json::Value>>>,
-    pub finalized_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub kind: String,
-    pub max_attempts: i16,
-    pub metadata: sqlx::types::Json<serde_json::Value>,
-    pub priority: i16,
-    pub queue: String,
-    pub state: sqlx::types::Json<serde_json::Value>,
-    pub scheduled_at: chrono::DateTime<chrono::Utc>,
-    pub tags: Vec<sqlx::types::Json<serde_json::Value>>,
-    pub unique_key: Option<Vec<u8>>,
-    pub unique_states: Option<sqlx::types::Json<serde_json::Value>>,
-}
-pub async fn job_get_stuck<'e, E>(
+/// Generated from `river_job.sql` (`:many`).
+pub fn job_get_by_id_many_stream<'e, E>(
     db: E,
-    stuck_horizon: chrono::DateTime<chrono::Utc>,
-    max: i32,
-) -> Result<Vec<JobGetStuckRow>, sqlx::Error>
+    id: Vec<i64>,
+) -> impl futures::Stream<Item = Result<RiverJob, sqlx::Error>> + 'e
 where
-    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+    E: sqlx::Executor<'e, Database = sqlx::Postgres> + 'e,
 {
-    let rec: Vec<JobGetStuckRow> = sqlx::query_as(
-            "
+    sqlx::query_as(JOB_GET_BY_ID_MANY).bind(id).fetch(db)
+}
+/// SQL of [`job_get_stuck`].
+pub const JOB_GET_STUCK: &str = "
 SELECT id, args, attempt, attempted_at, attempted_by, created_at, errors, finalized_at, kind, max_attempts, metadata, priority, queue, state, scheduled_at, tags, unique_key, unique_states
 FROM river_job
 WHERE state = 'running'
     AND attempted_at < $1::timestamptz
 ORDER BY id
 LIMIT $2
-",
-        )
+";
+/// Generated from `river_job.sql` (`:many`).
+pub async fn job_get_stuck<'e, E>(
+    db: E,
+    stuck_horizon: chrono::DateTime<chrono::Utc>,
+    max: i32,
+) -> Result<Vec<RiverJob>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: Vec<RiverJob> = sqlx::query_as(JOB_GET_STUCK)
         .bind(stuck_horizon)
         .bind(max)
         .fetch_all(db)
         .await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct JobInsertFastManyInfo {
-    pub args: Vec<sqlx::types::Json<serde_json::Value>>,
-    pub kind: Vec<String>,
-    pub max_attempts: Vec<i16>,
-    pub metadata: Vec<sqlx::types::Json<serde_json::Value>>,
-    pub priority: Vec<i16>,
-    pub queue: Vec<String>,
-    pub scheduled_at: Vec<chrono::DateTime<chrono::Utc>>,
-    pub state: Vec<String>,
-    pub tags: Vec<String>,
-    pub unique_key: Vec<Vec<u8>>,
-    pub unique_states: Vec<sqlx::types::Json<serde_json::Value>>,
-}
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct JobInsertFastManyRow {
-    pub river_job: Option<sqlx::types::Json<serde_json::Value>>,
-    pub unique_skipped_as_duplicate: bool,
-}
-pub async fn job_insert_fast_many<'e, E>(
+/// Generated from `river_job.sql` (`:many`).
+pub fn job_get_stuck_stream<'e, E>(
     db: E,
-    job_insert_fast_many_info: JobInsertFastManyInfo,
-) -> Result<Vec<JobInsertFastManyRow>, sqlx::Error>
+    stuck_horizon: chrono::DateTime<chrono::Utc>,
+    max: i32,
+) -> impl futures::Stream<Item = Result<RiverJob, sqlx::Error>> + 'e
 where
-    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+    E: sqlx::Executor<'e, Database = sqlx::Postgres> + 'e,
 {
-    let rec: Vec<JobInsertFastManyRow> = sqlx::query_as(
-            "
+    sqlx::query_as(JOB_GET_STUCK).bind(stuck_horizon).bind(max).fetch(db)
+}
+/// SQL of [`job_insert_fast_many`].
+pub const JOB_INSERT_FAST_MANY: &str = "
 INSERT INTO river_job(
     args,
     kind,
@@ -717,8 +765,63 @@ ON CONFLICT (unique_key)
     -- Something needs to be updated for a row to be returned on a conflict.
     DO UPDATE SET kind = EXCLUDED.kind
 RETURNING river_job.id, river_job.args, river_job.attempt, river_job.attempted_at, river_job.attempted_by, river_job.created_at, river_job.errors, river_job.finalized_at, river_job.kind, river_job.max_attempts, river_job.metadata, river_job.priority, river_job.queue, river_job.state, river_job.scheduled_at, river_job.tags, river_job.unique_key, river_job.unique_states, (xmax != 0) AS unique_skipped_as_duplicate
-",
-        )
+";
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct JobInsertFastManyInfo {
+    pub args: Vec<sqlx::types::Json<serde_json::Value>>,
+    pub kind: Vec<String>,
+    pub max_attempts: Vec<i16>,
+    pub metadata: Vec<sqlx::types::Json<serde_json::Value>>,
+    pub priority: Vec<i16>,
+    pub queue: Vec<String>,
+    pub scheduled_at: Vec<chrono::DateTime<chrono::Utc>>,
+    pub state: Vec<String>,
+    pub tags: Vec<String>,
+    pub unique_key: Vec<Vec<u8>>,
+    pub unique_states: Vec<bit_vec::BitVec>,
+}
+#[derive(Debug, Clone)]
+pub struct JobInsertFastManyRow {
+    pub river_job: RiverJob,
+    pub unique_skipped_as_duplicate: bool,
+}
+impl sqlx::FromRow<'_, sqlx::postgres::PgRow> for JobInsertFastManyRow {
+    fn from_row(row: &sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+        use sqlx::Row as _;
+        Ok(Self {
+            river_job: RiverJob {
+                id: row.try_get(0)?,
+                args: row.try_get(1)?,
+                attempt: row.try_get(2)?,
+                attempted_at: row.try_get(3)?,
+                attempted_by: row.try_get(4)?,
+                created_at: row.try_get(5)?,
+                errors: row.try_get(6)?,
+                finalized_at: row.try_get(7)?,
+                kind: row.try_get(8)?,
+                max_attempts: row.try_get(9)?,
+                metadata: row.try_get(10)?,
+                priority: row.try_get(11)?,
+                queue: row.try_get(12)?,
+                state: row.try_get(13)?,
+                scheduled_at: row.try_get(14)?,
+                tags: row.try_get(15)?,
+                unique_key: row.try_get(16)?,
+                unique_states: row.try_get(17)?,
+            },
+            unique_skipped_as_duplicate: row.try_get(18)?,
+        })
+    }
+}
+/// Generated from `river_job.sql` (`:many`).
+pub async fn job_insert_fast_many<'e, E>(
+    db: E,
+    job_insert_fast_many_info: JobInsertFastManyInfo,
+) -> Result<Vec<JobInsertFastManyRow>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: Vec<JobInsertFastManyRow> = sqlx::query_as(JOB_INSERT_FAST_MANY)
         .bind(job_insert_fast_many_info.args)
         .bind(job_insert_fast_many_info.kind)
         .bind(job_insert_fast_many_info.max_attempts)
@@ -734,29 +837,30 @@ RETURNING river_job.id, river_job.args, river_job.attempt, river_job.attempted_a
         .await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct JobInsertFastManyNoReturningInfo {
-    pub args: Vec<sqlx::types::Json<serde_json::Value>>,
-    pub kind: Vec<String>,
-    pub max_attempts: Vec<i16>,
-    pub metadata: Vec<sqlx::types::Json<serde_json::Value>>,
-    pub priority: Vec<i16>,
-    pub queue: Vec<String>,
-    pub scheduled_at: Vec<chrono::DateTime<chrono::Utc>>,
-    pub state: Vec<sqlx::types::Json<serde_json::Value>>,
-    pub tags: Vec<String>,
-    pub unique_key: Vec<Vec<u8>>,
-    pub unique_states: Vec<sqlx::types::Json<serde_json::Value>>,
-}
-pub async fn job_insert_fast_many_no_returning<'e, E>(
+/// Generated from `river_job.sql` (`:many`).
+pub fn job_insert_fast_many_stream<'e, E>(
     db: E,
-    job_insert_fast_many_no_returning_info: JobInsertFastManyNoReturningInfo,
-) -> Result<u64, sqlx::Error>
+    job_insert_fast_many_info: JobInsertFastManyInfo,
+) -> impl futures::Stream<Item = Result<JobInsertFastManyRow, sqlx::Error>> + 'e
 where
-    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+    E: sqlx::Executor<'e, Database = sqlx::Postgres> + 'e,
 {
-    let rec = sqlx::query(
-            "
+    sqlx::query_as(JOB_INSERT_FAST_MANY)
+        .bind(job_insert_fast_many_info.args)
+        .bind(job_insert_fast_many_info.kind)
+        .bind(job_insert_fast_many_info.max_attempts)
+        .bind(job_insert_fast_many_info.metadata)
+        .bind(job_insert_fast_many_info.priority)
+        .bind(job_insert_fast_many_info.queue)
+        .bind(job_insert_fast_many_info.scheduled_at)
+        .bind(job_insert_fast_many_info.state)
+        .bind(job_insert_fast_many_info.tags)
+        .bind(job_insert_fast_many_info.unique_key)
+        .bind(job_insert_fast_many_info.unique_states)
+        .fetch(db)
+}
+/// SQL of [`job_insert_fast_many_no_returning`].
+pub const JOB_INSERT_FAST_MANY_NO_RETURNING: &str = "
 INSERT INTO river_job(
     args,
     kind,
@@ -793,8 +897,30 @@ ON CONFLICT (unique_key)
       AND unique_states IS NOT NULL
       AND river_job_state_in_bitmask(unique_states, state)
 DO NOTHING
-",
-        )
+";
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct JobInsertFastManyNoReturningInfo {
+    pub args: Vec<sqlx::types::Json<serde_json::Value>>,
+    pub kind: Vec<String>,
+    pub max_attempts: Vec<i16>,
+    pub metadata: Vec<sqlx::types::Json<serde_json::Value>>,
+    pub priority: Vec<i16>,
+    pub queue: Vec<String>,
+    pub scheduled_at: Vec<chrono::DateTime<chrono::Utc>>,
+    pub state: Vec<RiverJobState>,
+    pub tags: Vec<String>,
+    pub unique_key: Vec<Vec<u8>>,
+    pub unique_states: Vec<bit_vec::BitVec>,
+}
+/// Generated from `river_job.sql` (`:execrows`).
+pub async fn job_insert_fast_many_no_returning<'e, E>(
+    db: E,
+    job_insert_fast_many_no_returning_info: JobInsertFastManyNoReturningInfo,
+) -> Result<u64, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec = sqlx::query(JOB_INSERT_FAST_MANY_NO_RETURNING)
         .bind(job_insert_fast_many_no_returning_info.args)
         .bind(job_insert_fast_many_no_returning_info.kind)
         .bind(job_insert_fast_many_no_returning_info.max_attempts)
@@ -810,55 +936,8 @@ DO NOTHING
         .await?;
     Ok(rec.rows_affected())
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct JobInsertFullInfo {
-    pub args: sqlx::types::Json<serde_json::Value>,
-    pub attempt: i16,
-    pub attempted_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub errors: Option<Vec<sqlx::types::Json<serde_json::Value>>>,
-    pub finalized_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub kind: String,
-    pub max_attempts: i16,
-    pub metadata: sqlx::types::Json<serde_json::Value>,
-    pub priority: i16,
-    pub queue: String,
-    pub scheduled_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub state: sqlx::types::Json<serde_json::Value>,
-    pub tags: Vec<sqlx::types::Json<serde_json::Value>>,
-    pub unique_key: Option<Vec<u8>>,
-    pub unique_states: Option<sqlx::types::Json<serde_json::Value>>,
-}
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct JobInsertFullRow {
-    pub id: i64,
-    pub args: sqlx::types::Json<serde_json::Value>,
-    pub attempt: i16,
-    pub attempted_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub attempted_by: Option<Vec<String>>,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub errors: Option<Vec<sqlx::types::Json<serde_json::Value>>>,
-    pub finalized_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub kind: String,
-    pub max_attempts: i16,
-    pub metadata: sqlx::types::Json<serde_json::Value>,
-    pub priority: i16,
-    pub queue: String,
-    pub state: sqlx::types::Json<serde_json::Value>,
-    pub scheduled_at: chrono::DateTime<chrono::Utc>,
-    pub tags: Vec<sqlx::types::Json<serde_json::Value>>,
-    pub unique_key: Option<Vec<u8>>,
-    pub unique_states: Option<sqlx::types::Json<serde_json::Value>>,
-}
-pub async fn job_insert_full<'e, E>(
-    db: E,
-    job_insert_full_info: JobInsertFullInfo,
-) -> Result<JobInsertFullRow, sqlx::Error>
-where
-    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
-{
-    let rec: JobInsertFullRow = sqlx::query_as(
-            "
+/// SQL of [`job_insert_full`].
+pub const JOB_INSERT_FULL: &str = "
 INSERT INTO river_job(
     args,
     attempt,
@@ -894,8 +973,35 @@ INSERT INTO river_job(
     $15,
     $16
 ) RETURNING id, args, attempt, attempted_at, attempted_by, created_at, errors, finalized_at, kind, max_attempts, metadata, priority, queue, state, scheduled_at, tags, unique_key, unique_states
-",
-        )
+";
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct JobInsertFullInfo {
+    pub args: sqlx::types::Json<serde_json::Value>,
+    pub attempt: i16,
+    pub attempted_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub created_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub errors: Option<Vec<sqlx::types::Json<serde_json::Value>>>,
+    pub finalized_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub kind: String,
+    pub max_attempts: i16,
+    pub metadata: sqlx::types::Json<serde_json::Value>,
+    pub priority: i16,
+    pub queue: String,
+    pub scheduled_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub state: RiverJobState,
+    pub tags: Vec<String>,
+    pub unique_key: Option<Vec<u8>>,
+    pub unique_states: Option<bit_vec::BitVec>,
+}
+/// Generated from `river_job.sql` (`:one`).
+pub async fn job_insert_full<'e, E>(
+    db: E,
+    job_insert_full_info: JobInsertFullInfo,
+) -> Result<RiverJob, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: RiverJob = sqlx::query_as(JOB_INSERT_FULL)
         .bind(job_insert_full_info.args)
         .bind(job_insert_full_info.attempt)
         .bind(job_insert_full_info.attempted_at)
@@ -916,23 +1022,37 @@ INSERT INTO river_job(
         .await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct JobRescueManyInfo {
-    pub id: Vec<i64>,
-    pub error: Vec<sqlx::types::Json<serde_json::Value>>,
-    pub finalized_at: Vec<chrono::DateTime<chrono::Utc>>,
-    pub scheduled_at: Vec<chrono::DateTime<chrono::Utc>>,
-    pub state: Vec<String>,
-}
-pub async fn job_rescue_many<'e, E>(
+/// Generated from `river_job.sql` (`:one`).
+pub async fn job_insert_full_optional<'e, E>(
     db: E,
-    job_rescue_many_info: JobRescueManyInfo,
-) -> Result<(), sqlx::Error>
+    job_insert_full_info: JobInsertFullInfo,
+) -> Result<Option<RiverJob>, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    sqlx::query(
-            "
+    let rec: Option<RiverJob> = sqlx::query_as(JOB_INSERT_FULL)
+        .bind(job_insert_full_info.args)
+        .bind(job_insert_full_info.attempt)
+        .bind(job_insert_full_info.attempted_at)
+        .bind(job_insert_full_info.created_at)
+        .bind(job_insert_full_info.errors)
+        .bind(job_insert_full_info.finalized_at)
+        .bind(job_insert_full_info.kind)
+        .bind(job_insert_full_info.max_attempts)
+        .bind(job_insert_full_info.metadata)
+        .bind(job_insert_full_info.priority)
+        .bind(job_insert_full_info.queue)
+        .bind(job_insert_full_info.scheduled_at)
+        .bind(job_insert_full_info.state)
+        .bind(job_insert_full_info.tags)
+        .bind(job_insert_full_info.unique_key)
+        .bind(job_insert_full_info.unique_states)
+        .fetch_optional(db)
+        .await?;
+    Ok(rec)
+}
+/// SQL of [`job_rescue_many`].
+pub const JOB_RESCUE_MANY: &str = "
 UPDATE river_job
 SET
     errors = array_append(errors, updated_job.error),
@@ -948,8 +1068,26 @@ FROM (
         unnest($5::text[])::river_job_state AS state
 ) AS updated_job
 WHERE river_job.id = updated_job.id
-",
-        )
+";
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct JobRescueManyInfo {
+    pub id: Vec<i64>,
+    pub error: Vec<sqlx::types::Json<serde_json::Value>>,
+    pub finalized_at: Vec<chrono::DateTime<chrono::Utc>>,
+    pub scheduled_at: Vec<chrono::DateTime<chrono::Utc>>,
+    pub state: Vec<String>,
+}
+/// Run by the rescuer to queue for retry or discard depending on job state.
+///
+/// Generated from `river_job.sql` (`:exec`).
+pub async fn job_rescue_many<'e, E>(
+    db: E,
+    job_rescue_many_info: JobRescueManyInfo,
+) -> Result<(), sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    sqlx::query(JOB_RESCUE_MANY)
         .bind(job_rescue_many_info.id)
         .bind(job_rescue_many_info.error)
         .bind(job_rescue_many_info.finalized_at)
@@ -959,33 +1097,8 @@ WHERE river_job.id = updated_job.id
         .await?;
     Ok(())
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct JobRetryRow {
-    pub id: i64,
-    pub args: sqlx::types::Json<serde_json::Value>,
-    pub attempt: i16,
-    pub attempted_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub attempted_by: Option<Vec<String>>,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub errors: Option<Vec<sqlx::types::Json<serde_json::Value>>>,
-    pub finalized_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub kind: String,
-    pub max_attempts: i16,
-    pub metadata: sqlx::types::Json<serde_json::Value>,
-    pub priority: i16,
-    pub queue: String,
-    pub state: sqlx::types::Json<serde_json::Value>,
-    pub scheduled_at: chrono::DateTime<chrono::Utc>,
-    pub tags: Vec<sqlx::types::Json<serde_json::Value>>,
-    pub unique_key: Option<Vec<u8>>,
-    pub unique_states: Option<sqlx::types::Json<serde_json::Value>>,
-}
-pub async fn job_retry<'e, E>(db: E, id: i64) -> Result<JobRetryRow, sqlx::Error>
-where
-    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
-{
-    let rec: JobRetryRow = sqlx::query_as(
-            "
+/// SQL of [`job_retry`].
+pub const JOB_RETRY: &str = "
 WITH job_to_update AS (
     SELECT id
     FROM river_job
@@ -1014,28 +1127,31 @@ WHERE id = $1::bigint
 UNION
 SELECT id, args, attempt, attempted_at, attempted_by, created_at, errors, finalized_at, kind, max_attempts, metadata, priority, queue, state, scheduled_at, tags, unique_key, unique_states
 FROM updated_job
-",
-        )
-        .bind(id)
-        .fetch_one(db)
-        .await?;
+";
+/// Generated from `river_job.sql` (`:one`).
+pub async fn job_retry<'e, E>(db: E, id: i64) -> Result<RiverJob, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: RiverJob = sqlx::query_as(JOB_RETRY).bind(id).fetch_one(db).await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct JobScheduleRow {
-    pub river_job: Option<sqlx::types::Json<serde_json::Value>>,
-    pub conflict_discarded: bool,
-}
-pub async fn job_schedule<'e, E>(
+/// Generated from `river_job.sql` (`:one`).
+pub async fn job_retry_optional<'e, E>(
     db: E,
-    now: chrono::DateTime<chrono::Utc>,
-    max: i64,
-) -> Result<Vec<JobScheduleRow>, sqlx::Error>
+    id: i64,
+) -> Result<Option<RiverJob>, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec: Vec<JobScheduleRow> = sqlx::query_as(
-            "
+    let rec: Option<RiverJob> = sqlx::query_as(JOB_RETRY)
+        .bind(id)
+        .fetch_optional(db)
+        .await?;
+    Ok(rec)
+}
+/// SQL of [`job_schedule`].
+pub const JOB_SCHEDULE: &str = "
 WITH jobs_to_schedule AS (
     SELECT
         id,
@@ -1115,45 +1231,69 @@ SELECT
     updated_jobs.conflict_discarded
 FROM river_job
 JOIN updated_jobs ON river_job.id = updated_jobs.id
-",
-        )
+";
+#[derive(Debug, Clone)]
+pub struct JobScheduleRow {
+    pub river_job: RiverJob,
+    pub conflict_discarded: bool,
+}
+impl sqlx::FromRow<'_, sqlx::postgres::PgRow> for JobScheduleRow {
+    fn from_row(row: &sqlx::postgres::PgRow) -> Result<Self, sqlx::Error> {
+        use sqlx::Row as _;
+        Ok(Self {
+            river_job: RiverJob {
+                id: row.try_get(0)?,
+                args: row.try_get(1)?,
+                attempt: row.try_get(2)?,
+                attempted_at: row.try_get(3)?,
+                attempted_by: row.try_get(4)?,
+                created_at: row.try_get(5)?,
+                errors: row.try_get(6)?,
+                finalized_at: row.try_get(7)?,
+                kind: row.try_get(8)?,
+                max_attempts: row.try_get(9)?,
+                metadata: row.try_get(10)?,
+                priority: row.try_get(11)?,
+                queue: row.try_get(12)?,
+                state: row.try_get(13)?,
+                scheduled_at: row.try_get(14)?,
+                tags: row.try_get(15)?,
+                unique_key: row.try_get(16)?,
+                unique_states: row.try_get(17)?,
+            },
+            conflict_discarded: row.try_get(18)?,
+        })
+    }
+}
+/// Generated from `river_job.sql` (`:many`).
+pub async fn job_schedule<'e, E>(
+    db: E,
+    now: chrono::DateTime<chrono::Utc>,
+    max: i64,
+) -> Result<Vec<JobScheduleRow>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: Vec<JobScheduleRow> = sqlx::query_as(JOB_SCHEDULE)
         .bind(now)
         .bind(max)
         .fetch_all(db)
         .await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct JobSetCompleteIfRunningManyRow {
-    pub id: i64,
-    pub args: sqlx::types::Json<serde_json::Value>,
-    pub attempt: i16,
-    pub attempted_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub attempted_by: Option<Vec<String>>,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub errors: Option<Vec<sqlx::types::Json<serde_json::Value>>>,
-    pub finalized_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub kind: String,
-    pub max_attempts: i16,
-    pub metadata: sqlx::types::Json<serde_json::Value>,
-    pub priority: i16,
-    pub queue: String,
-    pub state: sqlx::types::Json<serde_json::Value>,
-    pub scheduled_at: chrono::DateTime<chrono::Utc>,
-    pub tags: Vec<sqlx::types::Json<serde_json::Value>>,
-    pub unique_key: Option<Vec<u8>>,
-    pub unique_states: Option<sqlx::types::Json<serde_json::Value>>,
-}
-pub async fn job_set_complete_if_running_many<'e, E>(
+/// Generated from `river_job.sql` (`:many`).
+pub fn job_schedule_stream<'e, E>(
     db: E,
-    id: Vec<i64>,
-    finalized_at: Vec<chrono::DateTime<chrono::Utc>>,
-) -> Result<Vec<JobSetCompleteIfRunningManyRow>, sqlx::Error>
+    now: chrono::DateTime<chrono::Utc>,
+    max: i64,
+) -> impl futures::Stream<Item = Result<JobScheduleRow, sqlx::Error>> + 'e
 where
-    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+    E: sqlx::Executor<'e, Database = sqlx::Postgres> + 'e,
 {
-    let rec: Vec<JobSetCompleteIfRunningManyRow> = sqlx::query_as(
-            "
+    sqlx::query_as(JOB_SCHEDULE).bind(now).bind(max).fetch(db)
+}
+/// SQL of [`job_set_complete_if_running_many`].
+pub const JOB_SET_COMPLETE_IF_RUNNING_MANY: &str = "
 WITH job_to_finalized_at AS (
     SELECT
         unnest($1::bigint[]) AS id,
@@ -1181,57 +1321,39 @@ WHERE id IN (SELECT id FROM job_to_finalized_at EXCEPT SELECT id FROM updated_jo
 UNION
 SELECT id, args, attempt, attempted_at, attempted_by, created_at, errors, finalized_at, kind, max_attempts, metadata, priority, queue, state, scheduled_at, tags, unique_key, unique_states
 FROM updated_job
-",
-        )
+";
+/// Generated from `river_job.sql` (`:many`).
+pub async fn job_set_complete_if_running_many<'e, E>(
+    db: E,
+    id: Vec<i64>,
+    finalized_at: Vec<chrono::DateTime<chrono::Utc>>,
+) -> Result<Vec<RiverJob>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: Vec<RiverJob> = sqlx::query_as(JOB_SET_COMPLETE_IF_RUNNING_MANY)
         .bind(id)
         .bind(finalized_at)
         .fetch_all(db)
         .await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct JobSetStateIfRunningInfo {
-    pub state: sqlx::types::Json<serde_json::Value>,
-    pub id: i64,
-    pub finalized_at_do_update: bool,
-    pub finalized_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub error_do_update: bool,
-    pub error: sqlx::types::Json<serde_json::Value>,
-    pub max_attempts_update: bool,
-    pub max_attempts: i16,
-    pub scheduled_at_do_update: bool,
-    pub scheduled_at: Option<chrono::DateTime<chrono::Utc>>,
-}
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct JobSetStateIfRunningRow {
-    pub id: i64,
-    pub args: sqlx::types::Json<serde_json::Value>,
-    pub attempt: i16,
-    pub attempted_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub attempted_by: Option<Vec<String>>,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub errors: Option<Vec<sqlx::types::Json<serde_json::Value>>>,
-    pub finalized_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub kind: String,
-    pub max_attempts: i16,
-    pub metadata: sqlx::types::Json<serde_json::Value>,
-    pub priority: i16,
-    pub queue: String,
-    pub state: sqlx::types::Json<serde_json::Value>,
-    pub scheduled_at: chrono::DateTime<chrono::Utc>,
-    pub tags: Vec<sqlx::types::Json<serde_json::Value>>,
-    pub unique_key: Option<Vec<u8>>,
-    pub unique_states: Option<sqlx::types::Json<serde_json::Value>>,
-}
-pub async fn job_set_state_if_running<'e, E>(
+/// Generated from `river_job.sql` (`:many`).
+pub fn job_set_complete_if_running_many_stream<'e, E>(
     db: E,
-    job_set_state_if_running_info: JobSetStateIfRunningInfo,
-) -> Result<JobSetStateIfRunningRow, sqlx::Error>
+    id: Vec<i64>,
+    finalized_at: Vec<chrono::DateTime<chrono::Utc>>,
+) -> impl futures::Stream<Item = Result<RiverJob, sqlx::Error>> + 'e
 where
-    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+    E: sqlx::Executor<'e, Database = sqlx::Postgres> + 'e,
 {
-    let rec: JobSetStateIfRunningRow = sqlx::query_as(
-            "
+    sqlx::query_as(JOB_SET_COMPLETE_IF_RUNNING_MANY)
+        .bind(id)
+        .bind(finalized_at)
+        .fetch(db)
+}
+/// SQL of [`job_set_state_if_running`].
+pub const JOB_SET_STATE_IF_RUNNING: &str = "
 WITH job_to_update AS (
     SELECT
         id,
@@ -1266,8 +1388,29 @@ WHERE id = $2::bigint
 UNION
 SELECT id, args, attempt, attempted_at, attempted_by, created_at, errors, finalized_at, kind, max_attempts, metadata, priority, queue, state, scheduled_at, tags, unique_key, unique_states
 FROM updated_job
-",
-        )
+";
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct JobSetStateIfRunningInfo {
+    pub state: RiverJobState,
+    pub id: i64,
+    pub finalized_at_do_update: bool,
+    pub finalized_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub error_do_update: bool,
+    pub error: sqlx::types::Json<serde_json::Value>,
+    pub max_attempts_update: bool,
+    pub max_attempts: i16,
+    pub scheduled_at_do_update: bool,
+    pub scheduled_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+/// Generated from `river_job.sql` (`:one`).
+pub async fn job_set_state_if_running<'e, E>(
+    db: E,
+    job_set_state_if_running_info: JobSetStateIfRunningInfo,
+) -> Result<RiverJob, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: RiverJob = sqlx::query_as(JOB_SET_STATE_IF_RUNNING)
         .bind(job_set_state_if_running_info.state)
         .bind(job_set_state_if_running_info.id)
         .bind(job_set_state_if_running_info.finalized_at_do_update)
@@ -1282,49 +1425,31 @@ FROM updated_job
         .await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct JobSetStateIfRunningManyInfo {
-    pub ids: Vec<i64>,
-    pub state: Vec<String>,
-    pub finalized_at_do_update: Vec<bool>,
-    pub finalized_at: Vec<chrono::DateTime<chrono::Utc>>,
-    pub errors_do_update: Vec<bool>,
-    pub errors: Vec<sqlx::types::Json<serde_json::Value>>,
-    pub max_attempts_do_update: Vec<bool>,
-    pub max_attempts: Vec<i32>,
-    pub scheduled_at_do_update: Vec<bool>,
-    pub scheduled_at: Vec<chrono::DateTime<chrono::Utc>>,
-}
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct JobSetStateIfRunningManyRow {
-    pub id: i64,
-    pub args: sqlx::types::Json<serde_json::Value>,
-    pub attempt: i16,
-    pub attempted_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub attempted_by: Option<Vec<String>>,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub errors: Option<Vec<sqlx::types::Json<serde_json::Value>>>,
-    pub finalized_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub kind: String,
-    pub max_attempts: i16,
-    pub metadata: sqlx::types::Json<serde_json::Value>,
-    pub priority: i16,
-    pub queue: String,
-    pub state: sqlx::types::Json<serde_json::Value>,
-    pub scheduled_at: chrono::DateTime<chrono::Utc>,
-    pub tags: Vec<sqlx::types::Json<serde_json::Value>>,
-    pub unique_key: Option<Vec<u8>>,
-    pub unique_states: Option<sqlx::types::Json<serde_json::Value>>,
-}
-pub async fn job_set_state_if_running_many<'e, E>(
+/// Generated from `river_job.sql` (`:one`).
+pub async fn job_set_state_if_running_optional<'e, E>(
     db: E,
-    job_set_state_if_running_many_info: JobSetStateIfRunningManyInfo,
-) -> Result<Vec<JobSetStateIfRunningManyRow>, sqlx::Error>
+    job_set_state_if_running_info: JobSetStateIfRunningInfo,
+) -> Result<Option<RiverJob>, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec: Vec<JobSetStateIfRunningManyRow> = sqlx::query_as(
-            "
+    let rec: Option<RiverJob> = sqlx::query_as(JOB_SET_STATE_IF_RUNNING)
+        .bind(job_set_state_if_running_info.state)
+        .bind(job_set_state_if_running_info.id)
+        .bind(job_set_state_if_running_info.finalized_at_do_update)
+        .bind(job_set_state_if_running_info.finalized_at)
+        .bind(job_set_state_if_running_info.error_do_update)
+        .bind(job_set_state_if_running_info.error)
+        .bind(job_set_state_if_running_info.max_attempts_update)
+        .bind(job_set_state_if_running_info.max_attempts)
+        .bind(job_set_state_if_running_info.scheduled_at_do_update)
+        .bind(job_set_state_if_running_info.scheduled_at)
+        .fetch_optional(db)
+        .await?;
+    Ok(rec)
+}
+/// SQL of [`job_set_state_if_running_many`].
+pub const JOB_SET_STATE_IF_RUNNING_MANY: &str = "
 WITH job_input AS (
     SELECT
         unnest($1::bigint[]) AS id,
@@ -1383,8 +1508,29 @@ WHERE id IN (SELECT id FROM job_input)
 UNION
 SELECT id, args, attempt, attempted_at, attempted_by, created_at, errors, finalized_at, kind, max_attempts, metadata, priority, queue, state, scheduled_at, tags, unique_key, unique_states
 FROM updated_job
-",
-        )
+";
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct JobSetStateIfRunningManyInfo {
+    pub ids: Vec<i64>,
+    pub state: Vec<String>,
+    pub finalized_at_do_update: Vec<bool>,
+    pub finalized_at: Vec<chrono::DateTime<chrono::Utc>>,
+    pub errors_do_update: Vec<bool>,
+    pub errors: Vec<sqlx::types::Json<serde_json::Value>>,
+    pub max_attempts_do_update: Vec<bool>,
+    pub max_attempts: Vec<i32>,
+    pub scheduled_at_do_update: Vec<bool>,
+    pub scheduled_at: Vec<chrono::DateTime<chrono::Utc>>,
+}
+/// Generated from `river_job.sql` (`:many`).
+pub async fn job_set_state_if_running_many<'e, E>(
+    db: E,
+    job_set_state_if_running_many_info: JobSetStateIfRunningManyInfo,
+) -> Result<Vec<RiverJob>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: Vec<RiverJob> = sqlx::query_as(JOB_SET_STATE_IF_RUNNING_MANY)
         .bind(job_set_state_if_running_many_info.ids)
         .bind(job_set_state_if_running_many_info.state)
         .bind(job_set_state_if_running_many_info.finalized_at_do_update)
@@ -1399,6 +1545,39 @@ FROM updated_job
         .await?;
     Ok(rec)
 }
+/// Generated from `river_job.sql` (`:many`).
+pub fn job_set_state_if_running_many_stream<'e, E>(
+    db: E,
+    job_set_state_if_running_many_info: JobSetStateIfRunningManyInfo,
+) -> impl futures::Stream<Item = Result<RiverJob, sqlx::Error>> + 'e
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres> + 'e,
+{
+    sqlx::query_as(JOB_SET_STATE_IF_RUNNING_MANY)
+        .bind(job_set_state_if_running_many_info.ids)
+        .bind(job_set_state_if_running_many_info.state)
+        .bind(job_set_state_if_running_many_info.finalized_at_do_update)
+        .bind(job_set_state_if_running_many_info.finalized_at)
+        .bind(job_set_state_if_running_many_info.errors_do_update)
+        .bind(job_set_state_if_running_many_info.errors)
+        .bind(job_set_state_if_running_many_info.max_attempts_do_update)
+        .bind(job_set_state_if_running_many_info.max_attempts)
+        .bind(job_set_state_if_running_many_info.scheduled_at_do_update)
+        .bind(job_set_state_if_running_many_info.scheduled_at)
+        .fetch(db)
+}
+/// SQL of [`job_update`].
+pub const JOB_UPDATE: &str = "
+UPDATE river_job
+SET
+    attempt = CASE WHEN $1::boolean THEN $2 ELSE attempt END,
+    attempted_at = CASE WHEN $3::boolean THEN $4 ELSE attempted_at END,
+    errors = CASE WHEN $5::boolean THEN $6::jsonb[] ELSE errors END,
+    finalized_at = CASE WHEN $7::boolean THEN $8 ELSE finalized_at END,
+    state = CASE WHEN $9::boolean THEN $10 ELSE state END
+WHERE id = $11
+RETURNING id, args, attempt, attempted_at, attempted_by, created_at, errors, finalized_at, kind, max_attempts, metadata, priority, queue, state, scheduled_at, tags, unique_key, unique_states
+";
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct JobUpdateInfo {
     pub attempt_do_update: bool,
@@ -1410,50 +1589,21 @@ pub struct JobUpdateInfo {
     pub finalized_at_do_update: bool,
     pub finalized_at: Option<chrono::DateTime<chrono::Utc>>,
     pub state_do_update: bool,
-    pub state: sqlx::types::Json<serde_json::Value>,
+    pub state: RiverJobState,
     pub id: i64,
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct JobUpdateRow {
-    pub id: i64,
-    pub args: sqlx::types::Json<serde_json::Value>,
-    pub attempt: i16,
-    pub attempted_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub attempted_by: Option<Vec<String>>,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub errors: Option<Vec<sqlx::types::Json<serde_json::Value>>>,
-    pub finalized_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub kind: String,
-    pub max_attempts: i16,
-    pub metadata: sqlx::types::Json<serde_json::Value>,
-    pub priority: i16,
-    pub queue: String,
-    pub state: sqlx::types::Json<serde_json::Value>,
-    pub scheduled_at: chrono::DateTime<chrono::Utc>,
-    pub tags: Vec<sqlx::types::Json<serde_json::Value>>,
-    pub unique_key: Option<Vec<u8>>,
-    pub unique_states: Option<sqlx::types::Json<serde_json::Value>>,
-}
+/// A generalized update for any property on a job. This brings in a large number
+/// of parameters and therefore may be more suitable for testing than production.
+///
+/// Generated from `river_job.sql` (`:one`).
 pub async fn job_update<'e, E>(
     db: E,
     job_update_info: JobUpdateInfo,
-) -> Result<JobUpdateRow, sqlx::Error>
+) -> Result<RiverJob, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec: JobUpdateRow = sqlx::query_as(
-            "
-UPDATE river_job
-SET
-    attempt = CASE WHEN $1::boolean THEN $2 ELSE attempt END,
-    attempted_at = CASE WHEN $3::boolean THEN $4 ELSE attempted_at END,
-    errors = CASE WHEN $5::boolean THEN $6::jsonb[] ELSE errors END,
-    finalized_at = CASE WHEN $7::boolean THEN $8 ELSE finalized_at END,
-    state = CASE WHEN $9::boolean THEN $10 ELSE state END
-WHERE id = $11
-RETURNING id, args, attempt, attempted_at, attempted_by, created_at, errors, finalized_at, kind, max_attempts, metadata, priority, queue, state, scheduled_at, tags, unique_key, unique_states
-",
-        )
+    let rec: RiverJob = sqlx::query_as(JOB_UPDATE)
         .bind(job_update_info.attempt_do_update)
         .bind(job_update_info.attempt)
         .bind(job_update_info.attempted_at_do_update)
@@ -1469,30 +1619,35 @@ RETURNING id, args, attempt, attempted_at, attempted_by, created_at, errors, fin
         .await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct JobInsertFastManyCopyFromInfo {
-    pub args: sqlx::types::Json<serde_json::Value>,
-    pub finalized_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub kind: String,
-    pub max_attempts: i16,
-    pub metadata: sqlx::types::Json<serde_json::Value>,
-    pub priority: i16,
-    pub queue: String,
-    pub scheduled_at: chrono::DateTime<chrono::Utc>,
-    pub state: sqlx::types::Json<serde_json::Value>,
-    pub tags: Vec<String>,
-    pub unique_key: Option<Vec<u8>>,
-    pub unique_states: Option<sqlx::types::Json<serde_json::Value>>,
-}
-pub async fn job_insert_fast_many_copy_from<'e, E>(
+/// A generalized update for any property on a job. This brings in a large number
+/// of parameters and therefore may be more suitable for testing than production.
+///
+/// Generated from `river_job.sql` (`:one`).
+pub async fn job_update_optional<'e, E>(
     db: E,
-    job_insert_fast_many_copy_from_info: JobInsertFastManyCopyFromInfo,
-) -> Result<(), sqlx::Error>
+    job_update_info: JobUpdateInfo,
+) -> Result<Option<RiverJob>, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    sqlx::query(
-            "
+    let rec: Option<RiverJob> = sqlx::query_as(JOB_UPDATE)
+        .bind(job_update_info.attempt_do_update)
+        .bind(job_update_info.attempt)
+        .bind(job_update_info.attempted_at_do_update)
+        .bind(job_update_info.attempted_at)
+        .bind(job_update_info.errors_do_update)
+        .bind(job_update_info.errors)
+        .bind(job_update_info.finalized_at_do_update)
+        .bind(job_update_info.finalized_at)
+        .bind(job_update_info.state_do_update)
+        .bind(job_update_info.state)
+        .bind(job_update_info.id)
+        .fetch_optional(db)
+        .await?;
+    Ok(rec)
+}
+/// SQL of [`job_insert_fast_many_copy_from`].
+pub const JOB_INSERT_FAST_MANY_COPY_FROM: &str = "
 INSERT INTO river_job(
     args,
     finalized_at,
@@ -1520,58 +1675,87 @@ INSERT INTO river_job(
     $11,
     $12
 )
-",
+";
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct JobInsertFastManyCopyFromInfo {
+    pub args: sqlx::types::Json<serde_json::Value>,
+    pub finalized_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub kind: String,
+    pub max_attempts: i16,
+    pub metadata: sqlx::types::Json<serde_json::Value>,
+    pub priority: i16,
+    pub queue: String,
+    pub scheduled_at: chrono::DateTime<chrono::Utc>,
+    pub state: RiverJobState,
+    pub tags: Vec<String>,
+    pub unique_key: Option<Vec<u8>>,
+    pub unique_states: Option<bit_vec::BitVec>,
+}
+/// Generated from `river_job_copyfrom.sql` (`:copyfrom`).
+pub async fn job_insert_fast_many_copy_from(
+    db: &mut sqlx::PgConnection,
+    rows: impl IntoIterator<Item = JobInsertFastManyCopyFromInfo>,
+) -> Result<u64, sqlx::Error> {
+    let mut copy = db
+        .copy_in_raw(
+            "COPY \"river_job\" (\"args\", \"finalized_at\", \"kind\", \"max_attempts\", \"metadata\", \"priority\", \"queue\", \"scheduled_at\", \"state\", \"tags\", \"unique_key\", \"unique_states\") FROM STDIN (FORMAT binary)",
         )
-        .bind(job_insert_fast_many_copy_from_info.args)
-        .bind(job_insert_fast_many_copy_from_info.finalized_at)
-        .bind(job_insert_fast_many_copy_from_info.kind)
-        .bind(job_insert_fast_many_copy_from_info.max_attempts)
-        .bind(job_insert_fast_many_copy_from_info.metadata)
-        .bind(job_insert_fast_many_copy_from_info.priority)
-        .bind(job_insert_fast_many_copy_from_info.queue)
-        .bind(job_insert_fast_many_copy_from_info.scheduled_at)
-        .bind(job_insert_fast_many_copy_from_info.state)
-        .bind(job_insert_fast_many_copy_from_info.tags)
-        .bind(job_insert_fast_many_copy_from_info.unique_key)
-        .bind(job_insert_fast_many_copy_from_info.unique_states)
-        .execute(db)
         .await?;
-    Err(sqlx::Error::TypeNotFound {
-        type_name: String::from(":copyfrom"),
-    })
-}
+    let mut buf = sqlx::postgres::PgArgumentBuffer::default();
+    buf.extend_from_slice(b"PGCOPY\x0a\xff\x0d\x0a\x00\x00\x00\x00\x00\x00\x00\x00\x00");
+    for row in rows {
+        buf.extend_from_slice(&12i16.to_be_bytes());
+        let encoded = Ok(())
+            .and_then(|()| copy_in_field(&mut buf, row.args))
+            .and_then(|()| copy_in_field(&mut buf, row.finalized_at))
+            .and_then(|()| copy_in_field(&mut buf, row.kind))
+            .and_then(|()| copy_in_field(&mut buf, row.max_attempts))
+            .and_then(|()| copy_in_field(&mut buf, row.metadata))
+            .and_then(|()| copy_in_field(&mut buf, row.priority))
+            .and_then(|()| copy_in_field(&mut buf, row.queue))
+            .and_then(|()| copy_in_field(&mut buf, row.scheduled_at))
+            .and_then(|()| copy_in_field(&mut buf, row.state))
+            .and_then(|()| copy_in_array_field(&mut buf, row.tags, 1043u32))
+            .and_then(|()| copy_in_field(&mut buf, row.unique_key))
+            .and_then(|()| copy_in_field(&mut buf, row.unique_states));
+        if let Err(err) = encoded {
+            copy.abort(err.to_string()).await?;
+            return Err(sqlx::Error::Encode(err));
+        }
+        if buf.len() >= 65_536 {
+            copy.send(buf.as_slice()).await?;
+            buf.clear();
+        }
+    }
+    buf.extend_from_slice(&(-1_i16).to_be_bytes());
+    copy.send(buf.as_slice()).await?;
+    copy.finish().await
+}
+/// SQL of [`leader_attempt_elect`].
+pub const LEADER_ATTEMPT_ELECT: &str = "
+INSERT INTO river_leader(leader_id, elected_at, expires_at)
+    VALUES ($1, now(), now() + $2::interval)
+ON CONFLICT (name)
+    DO NOTHING
+";
+/// Generated from `river_leader.sql` (`:execrows`).
 pub async fn leader_attempt_elect<'e, E>(
     db: E,
     leader_id: String,
-    ttl: chrono::Duration,
+    ttl: sqlx::postgres::types::PgInterval,
 ) -> Result<u64, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec = sqlx::query(
-            "
-INSERT INTO river_leader(leader_id, elected_at, expires_at)
-    VALUES ($1, now(), now() + $2::interval)
-ON CONFLICT (name)
-    DO NOTHING
-",
-        )
+    let rec = sqlx::query(LEADER_ATTEMPT_ELECT)
         .bind(leader_id)
         .bind(ttl)
         .execute(db)
         .await?;
     Ok(rec.rows_affected())
 }
-pub async fn leader_attempt_reelect<'e, E>(
-    db: E,
-    leader_id: String,
-    ttl: chrono::Duration,
-) -> Result<u64, sqlx::Error>
-where
-    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
-{
-    let rec = sqlx::query(
-            "
+/// SQL of [`leader_attempt_reelect`].
+pub const LEADER_ATTEMPT_REELECT: &str = "
 INSERT INTO river_leader(leader_id, elected_at, expires_at)
     VALUES ($1, now(), now() + $2::interval)
 ON CONFLICT (name)
@@ -1579,73 +1763,65 @@ ON CONFLICT (name)
         expires_at = now() + $2
     WHERE
         river_leader.leader_id = $1
-",
-        )
+";
+/// Generated from `river_leader.sql` (`:execrows`).
+pub async fn leader_attempt_reelect<'e, E>(
+    db: E,
+    leader_id: String,
+    ttl: sqlx::postgres::types::PgInterval,
+) -> Result<u64, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec = sqlx::query(LEADER_ATTEMPT_REELECT)
         .bind(leader_id)
         .bind(ttl)
         .execute(db)
         .await?;
     Ok(rec.rows_affected())
 }
+/// SQL of [`leader_delete_expired`].
+pub const LEADER_DELETE_EXPIRED: &str = "
+DELETE FROM river_leader
+WHERE expires_at < now()
+";
+/// Generated from `river_leader.sql` (`:execrows`).
 pub async fn leader_delete_expired<'e, E>(db: E) -> Result<u64, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec = sqlx::query("
-DELETE FROM river_leader
-WHERE expires_at < now()
-"
-    )
-        .execute(db)
-        .await?;
+    let rec = sqlx::query(LEADER_DELETE_EXPIRED).execute(db).await?;
     Ok(rec.rows_affected())
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct LeaderGetElectedLeaderRow {
-    pub elected_at: chrono::DateTime<chrono::Utc>,
-    pub expires_at: chrono::DateTime<chrono::Utc>,
-    pub leader_id: String,
-    pub name: String,
-}
-pub async fn leader_get_elected_leader<'e, E>(
-    db: E,
-) -> Result<LeaderGetElectedLeaderRow, sqlx::Error>
+/// SQL of [`leader_get_elected_leader`].
+pub const LEADER_GET_ELECTED_LEADER: &str = "
+SELECT elected_at, expires_at, leader_id, name
+FROM river_leader
+";
+/// Generated from `river_leader.sql` (`:one`).
+pub async fn leader_get_elected_leader<'e, E>(db: E) -> Result<RiverLeader, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec: LeaderGetElectedLeaderRow = sqlx::query_as(
-            "
-SELECT elected_at, expires_at, leader_id, name
-FROM river_leader
-",
-        )
+    let rec: RiverLeader = sqlx::query_as(LEADER_GET_ELECTED_LEADER)
         .fetch_one(db)
         .await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct LeaderInsertInfo {
-    pub elected_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub ttl: chrono::Duration,
-    pub leader_id: String,
-}
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct LeaderInsertRow {
-    pub elected_at: chrono::DateTime<chrono::Utc>,
-    pub expires_at: chrono::DateTime<chrono::Utc>,
-    pub leader_id: String,
-    pub name: String,
-}
-pub async fn leader_insert<'e, E>(
+/// Generated from `river_leader.sql` (`:one`).
+pub async fn leader_get_elected_leader_optional<'e, E>(
     db: E,
-    leader_insert_info: LeaderInsertInfo,
-) -> Result<LeaderInsertRow, sqlx::Error>
+) -> Result<Option<RiverLeader>, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec: LeaderInsertRow = sqlx::query_as(
-            "
+    let rec: Option<RiverLeader> = sqlx::query_as(LEADER_GET_ELECTED_LEADER)
+        .fetch_optional(db)
+        .await?;
+    Ok(rec)
+}
+/// SQL of [`leader_insert`].
+pub const LEADER_INSERT: &str = "
 INSERT INTO river_leader(
     elected_at,
     expires_at,
@@ -1655,8 +1831,23 @@ INSERT INTO river_leader(
     coalesce($2::timestamptz, now() + $3::interval),
     $4
 ) RETURNING elected_at, expires_at, leader_id, name
-",
-        )
+";
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct LeaderInsertInfo {
+    pub elected_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub ttl: sqlx::postgres::types::PgInterval,
+    pub leader_id: String,
+}
+/// Generated from `river_leader.sql` (`:one`).
+pub async fn leader_insert<'e, E>(
+    db: E,
+    leader_insert_info: LeaderInsertInfo,
+) -> Result<RiverLeader, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: RiverLeader = sqlx::query_as(LEADER_INSERT)
         .bind(leader_insert_info.elected_at)
         .bind(leader_insert_info.expires_at)
         .bind(leader_insert_info.ttl)
@@ -1665,16 +1856,25 @@ INSERT INTO river_leader(
         .await?;
     Ok(rec)
 }
-pub async fn leader_resign<'e, E>(
+/// Generated from `river_leader.sql` (`:one`).
+pub async fn leader_insert_optional<'e, E>(
     db: E,
-    leader_id: String,
-    leadership_topic: String,
-) -> Result<u64, sqlx::Error>
+    leader_insert_info: LeaderInsertInfo,
+) -> Result<Option<RiverLeader>, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec = sqlx::query(
-            "
+    let rec: Option<RiverLeader> = sqlx::query_as(LEADER_INSERT)
+        .bind(leader_insert_info.elected_at)
+        .bind(leader_insert_info.expires_at)
+        .bind(leader_insert_info.ttl)
+        .bind(leader_insert_info.leader_id)
+        .fetch_optional(db)
+        .await?;
+    Ok(rec)
+}
+/// SQL of [`leader_resign`].
+pub const LEADER_RESIGN: &str = "
 WITH currently_held_leaders AS (
   SELECT elected_at, expires_at, leader_id, name
   FROM river_leader
@@ -1689,19 +1889,37 @@ notified_resignations AS (
     FROM currently_held_leaders
 )
 DELETE FROM river_leader USING notified_resignations
-",
-        )
+";
+/// Generated from `river_leader.sql` (`:execrows`).
+pub async fn leader_resign<'e, E>(
+    db: E,
+    leader_id: String,
+    leadership_topic: String,
+) -> Result<u64, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec = sqlx::query(LEADER_RESIGN)
         .bind(leader_id)
         .bind(leadership_topic)
         .execute(db)
         .await?;
     Ok(rec.rows_affected())
 }
+/// SQL of [`river_migration_delete_assuming_main_many`].
+pub const RIVER_MIGRATION_DELETE_ASSUMING_MAIN_MANY: &str = "
+DELETE FROM river_migration
+WHERE version = any($1::bigint[])
+RETURNING
+    created_at,
+    version
+";
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct RiverMigrationDeleteAssumingMainManyRow {
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub version: i64,
 }
+/// Generated from `river_migration.sql` (`:many`).
 pub async fn river_migration_delete_assuming_main_many<'e, E>(
     db: E,
     version: Vec<i64>,
@@ -1710,40 +1928,43 @@ where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
     let rec: Vec<RiverMigrationDeleteAssumingMainManyRow> = sqlx::query_as(
-            "
-DELETE FROM river_migration
-WHERE version = any($1::bigint[])
-RETURNING
-    created_at,
-    version
-",
+            RIVER_MIGRATION_DELETE_ASSUMING_MAIN_MANY,
         )
         .bind(version)
         .fetch_all(db)
         .await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct RiverMigrationDeleteByLineAndVersionManyRow {
-    pub line: String,
-    pub version: i64,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-}
-pub async fn river_migration_delete_by_line_and_version_many<'e, E>(
+/// Generated from `river_migration.sql` (`:many`).
+pub fn river_migration_delete_assuming_main_many_stream<'e, E>(
     db: E,
-    line: String,
     version: Vec<i64>,
-) -> Result<Vec<RiverMigrationDeleteByLineAndVersionManyRow>, sqlx::Error>
+) -> impl futures::Stream<
+    Item = Result<RiverMigrationDeleteAssumingMainManyRow, sqlx::Error>,
+> + 'e
 where
-    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+    E: sqlx::Executor<'e, Database = sqlx::Postgres> + 'e,
 {
-    let rec: Vec<RiverMigrationDeleteByLineAndVersionManyRow> = sqlx::query_as(
-            "
+    sqlx::query_as(RIVER_MIGRATION_DELETE_ASSUMING_MAIN_MANY).bind(version).fetch(db)
+}
+/// SQL of [`river_migration_delete_by_line_and_version_many`].
+pub const RIVER_MIGRATION_DELETE_BY_LINE_AND_VERSION_MANY: &str = "
 DELETE FROM river_migration
 WHERE line = $1
     AND version = any($2::bigint[])
 RETURNING line, version, created_at
-",
+";
+/// Generated from `river_migration.sql` (`:many`).
+pub async fn river_migration_delete_by_line_and_version_many<'e, E>(
+    db: E,
+    line: String,
+    version: Vec<i64>,
+) -> Result<Vec<RiverMigration>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: Vec<RiverMigration> = sqlx::query_as(
+            RIVER_MIGRATION_DELETE_BY_LINE_AND_VERSION_MANY,
         )
         .bind(line)
         .bind(version)
@@ -1751,72 +1972,103 @@ RETURNING line, version, created_at
         .await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct RiverMigrationGetAllAssumingMainRow {
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub version: i64,
-}
-pub async fn river_migration_get_all_assuming_main<'e, E>(
+/// Generated from `river_migration.sql` (`:many`).
+pub fn river_migration_delete_by_line_and_version_many_stream<'e, E>(
     db: E,
-) -> Result<Vec<RiverMigrationGetAllAssumingMainRow>, sqlx::Error>
+    line: String,
+    version: Vec<i64>,
+) -> impl futures::Stream<Item = Result<RiverMigration, sqlx::Error>> + 'e
 where
-    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+    E: sqlx::Executor<'e, Database = sqlx::Postgres> + 'e,
 {
-    let rec: Vec<RiverMigrationGetAllAssumingMainRow> = sqlx::query_as(
-            "
+    sqlx::query_as(RIVER_MIGRATION_DELETE_BY_LINE_AND_VERSION_MANY)
+        .bind(line)
+        .bind(version)
+        .fetch(db)
+}
+/// SQL of [`river_migration_get_all_assuming_main`].
+pub const RIVER_MIGRATION_GET_ALL_ASSUMING_MAIN: &str = "
 SELECT
     created_at,
     version
 FROM river_migration
 ORDER BY version
-",
+";
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct RiverMigrationGetAllAssumingMainRow {
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub version: i64,
+}
+/// This is a compatibility query for getting existing migrations before the
+/// `line` column was added to the table in version 005. We need to make sure to
+/// only select non-line properties so the query doesn't error on older schemas.
+/// (Even if we use `SELECT *` below, sqlc materializes it to a list of column
+/// names in the generated query.)
+///
+/// Generated from `river_migration.sql` (`:many`).
+pub async fn river_migration_get_all_assuming_main<'e, E>(
+    db: E,
+) -> Result<Vec<RiverMigrationGetAllAssumingMainRow>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: Vec<RiverMigrationGetAllAssumingMainRow> = sqlx::query_as(
+            RIVER_MIGRATION_GET_ALL_ASSUMING_MAIN,
         )
         .fetch_all(db)
         .await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct RiverMigrationGetByLineRow {
-    pub line: String,
-    pub version: i64,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-}
-pub async fn river_migration_get_by_line<'e, E>(
+/// This is a compatibility query for getting existing migrations before the
+/// `line` column was added to the table in version 005. We need to make sure to
+/// only select non-line properties so the query doesn't error on older schemas.
+/// (Even if we use `SELECT *` below, sqlc materializes it to a list of column
+/// names in the generated query.)
+///
+/// Generated from `river_migration.sql` (`:many`).
+pub fn river_migration_get_all_assuming_main_stream<'e, E>(
     db: E,
-    line: String,
-) -> Result<Vec<RiverMigrationGetByLineRow>, sqlx::Error>
+) -> impl futures::Stream<
+    Item = Result<RiverMigrationGetAllAssumingMainRow, sqlx::Error>,
+> + 'e
 where
-    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+    E: sqlx::Executor<'e, Database = sqlx::Postgres> + 'e,
 {
-    let rec: Vec<RiverMigrationGetByLineRow> = sqlx::query_as(
-            "
+    sqlx::query_as(RIVER_MIGRATION_GET_ALL_ASSUMING_MAIN).fetch(db)
+}
+/// SQL of [`river_migration_get_by_line`].
+pub const RIVER_MIGRATION_GET_BY_LINE: &str = "
 SELECT line, version, created_at
 FROM river_migration
 WHERE line = $1
 ORDER BY version
-",
-        )
+";
+/// Generated from `river_migration.sql` (`:many`).
+pub async fn river_migration_get_by_line<'e, E>(
+    db: E,
+    line: String,
+) -> Result<Vec<RiverMigration>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: Vec<RiverMigration> = sqlx::query_as(RIVER_MIGRATION_GET_BY_LINE)
         .bind(line)
         .fetch_all(db)
         .await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct RiverMigrationInsertRow {
-    pub line: String,
-    pub version: i64,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-}
-pub async fn river_migration_insert<'e, E>(
+/// Generated from `river_migration.sql` (`:many`).
+pub fn river_migration_get_by_line_stream<'e, E>(
     db: E,
     line: String,
-    version: i64,
-) -> Result<RiverMigrationInsertRow, sqlx::Error>
+) -> impl futures::Stream<Item = Result<RiverMigration, sqlx::Error>> + 'e
 where
-    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+    E: sqlx::Executor<'e, Database = sqlx::Postgres> + 'e,
 {
-    let rec: RiverMigrationInsertRow = sqlx::query_as(
-            "
+    sqlx::query_as(RIVER_MIGRATION_GET_BY_LINE).bind(line).fetch(db)
+}
+/// SQL of [`river_migration_insert`].
+pub const RIVER_MIGRATION_INSERT: &str = "
 INSERT INTO river_migration (
     line,
     version
@@ -1824,30 +2076,41 @@ INSERT INTO river_migration (
     $1,
     $2
 ) RETURNING line, version, created_at
-",
-        )
+";
+/// Generated from `river_migration.sql` (`:one`).
+pub async fn river_migration_insert<'e, E>(
+    db: E,
+    line: String,
+    version: i64,
+) -> Result<RiverMigration, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: RiverMigration = sqlx::query_as(RIVER_MIGRATION_INSERT)
         .bind(line)
         .bind(version)
         .fetch_one(db)
         .await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct RiverMigrationInsertManyRow {
-    pub line: String,
-    pub version: i64,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-}
-pub async fn river_migration_insert_many<'e, E>(
+/// Generated from `river_migration.sql` (`:one`).
+pub async fn river_migration_insert_optional<'e, E>(
     db: E,
     line: String,
-    version: Vec<i64>,
-) -> Result<Vec<RiverMigrationInsertManyRow>, sqlx::Error>
+    version: i64,
+) -> Result<Option<RiverMigration>, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec: Vec<RiverMigrationInsertManyRow> = sqlx::query_as(
-            "
+    let rec: Option<RiverMigration> = sqlx::query_as(RIVER_MIGRATION_INSERT)
+        .bind(line)
+        .bind(version)
+        .fetch_optional(db)
+        .await?;
+    Ok(rec)
+}
+/// SQL of [`river_migration_insert_many`].
+pub const RIVER_MIGRATION_INSERT_MANY: &str = "
 INSERT INTO river_migration (
     line,
     version
@@ -1856,19 +2119,51 @@ SELECT
     $1,
     unnest($2::bigint[])
 RETURNING line, version, created_at
-",
-        )
+";
+/// Generated from `river_migration.sql` (`:many`).
+pub async fn river_migration_insert_many<'e, E>(
+    db: E,
+    line: String,
+    version: Vec<i64>,
+) -> Result<Vec<RiverMigration>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: Vec<RiverMigration> = sqlx::query_as(RIVER_MIGRATION_INSERT_MANY)
         .bind(line)
         .bind(version)
         .fetch_all(db)
         .await?;
     Ok(rec)
 }
+/// Generated from `river_migration.sql` (`:many`).
+pub fn river_migration_insert_many_stream<'e, E>(
+    db: E,
+    line: String,
+    version: Vec<i64>,
+) -> impl futures::Stream<Item = Result<RiverMigration, sqlx::Error>> + 'e
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres> + 'e,
+{
+    sqlx::query_as(RIVER_MIGRATION_INSERT_MANY).bind(line).bind(version).fetch(db)
+}
+/// SQL of [`river_migration_insert_many_assuming_main`].
+pub const RIVER_MIGRATION_INSERT_MANY_ASSUMING_MAIN: &str = "
+INSERT INTO river_migration (
+    version
+)
+SELECT
+    unnest($1::bigint[])
+RETURNING
+    created_at,
+    version
+";
 #[derive(Debug, Clone, sqlx::FromRow)]
 pub struct RiverMigrationInsertManyAssumingMainRow {
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub version: i64,
 }
+/// Generated from `river_migration.sql` (`:many`).
 pub async fn river_migration_insert_many_assuming_main<'e, E>(
     db: E,
     version: Vec<i64>,
@@ -1877,32 +2172,27 @@ where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
     let rec: Vec<RiverMigrationInsertManyAssumingMainRow> = sqlx::query_as(
-            "
-INSERT INTO river_migration (
-    version
-)
-SELECT
-    unnest($1::bigint[])
-RETURNING
-    created_at,
-    version
-",
+            RIVER_MIGRATION_INSERT_MANY_ASSUMING_MAIN,
         )
         .bind(version)
         .fetch_all(db)
         .await?;
     Ok(rec)
 }
-pub async fn column_exists<'e, E>(
+/// Generated from `river_migration.sql` (`:many`).
+pub fn river_migration_insert_many_assuming_main_stream<'e, E>(
     db: E,
-    table_name: String,
-    column_name: String,
-) -> Result<bool, sqlx::Error>
+    version: Vec<i64>,
+) -> impl futures::Stream<
+    Item = Result<RiverMigrationInsertManyAssumingMainRow, sqlx::Error>,
+> + 'e
 where
-    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+    E: sqlx::Executor<'e, Database = sqlx::Postgres> + 'e,
 {
-    let rec: bool = sqlx::query_scalar(
-            "
+    sqlx::query_as(RIVER_MIGRATION_INSERT_MANY_ASSUMING_MAIN).bind(version).fetch(db)
+}
+/// SQL of [`column_exists`].
+pub const COLUMN_EXISTS: &str = "
 SELECT EXISTS (
     SELECT column_name
     FROM information_schema.columns 
@@ -1910,53 +2200,71 @@ SELECT EXISTS (
         AND table_schema = CURRENT_SCHEMA
         AND column_name = $2::text
 )
-",
-        )
+";
+/// Generated from `river_migration.sql` (`:one`).
+pub async fn column_exists<'e, E>(
+    db: E,
+    table_name: String,
+    column_name: String,
+) -> Result<bool, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: bool = sqlx::query_scalar(COLUMN_EXISTS)
         .bind(table_name)
         .bind(column_name)
         .fetch_one(db)
         .await?;
     Ok(rec)
 }
-pub async fn table_exists<'e, E>(db: E, table_name: String) -> Result<bool, sqlx::Error>
+/// Generated from `river_migration.sql` (`:one`).
+pub async fn column_exists_optional<'e, E>(
+    db: E,
+    table_name: String,
+    column_name: String,
+) -> Result<Option<bool>, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec: bool = sqlx::query_scalar(
-            "
+    let rec: Option<bool> = sqlx::query_scalar(COLUMN_EXISTS)
+        .bind(table_name)
+        .bind(column_name)
+        .fetch_optional(db)
+        .await?;
+    Ok(rec)
+}
+/// SQL of [`table_exists`].
+pub const TABLE_EXISTS: &str = "
 SELECT CASE WHEN to_regclass($1) IS NULL THEN false
             ELSE true END
-",
-        )
+";
+/// Generated from `river_migration.sql` (`:one`).
+pub async fn table_exists<'e, E>(db: E, table_name: String) -> Result<bool, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: bool = sqlx::query_scalar(TABLE_EXISTS)
         .bind(table_name)
         .fetch_one(db)
         .await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct QueueCreateOrSetUpdatedAtInfo {
-    pub metadata: sqlx::types::Json<serde_json::Value>,
-    pub name: String,
-    pub paused_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
-}
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct QueueCreateOrSetUpdatedAtRow {
-    pub name: String,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub metadata: sqlx::types::Json<serde_json::Value>,
-    pub paused_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub updated_at: chrono::DateTime<chrono::Utc>,
-}
-pub async fn queue_create_or_set_updated_at<'e, E>(
+/// Generated from `river_migration.sql` (`:one`).
+pub async fn table_exists_optional<'e, E>(
     db: E,
-    queue_create_or_set_updated_at_info: QueueCreateOrSetUpdatedAtInfo,
-) -> Result<QueueCreateOrSetUpdatedAtRow, sqlx::Error>
+    table_name: String,
+) -> Result<Option<bool>, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec: QueueCreateOrSetUpdatedAtRow = sqlx::query_as(
-            "
+    let rec: Option<bool> = sqlx::query_scalar(TABLE_EXISTS)
+        .bind(table_name)
+        .fetch_optional(db)
+        .await?;
+    Ok(rec)
+}
+/// SQL of [`queue_create_or_set_updated_at`].
+pub const QUEUE_CREATE_OR_SET_UPDATED_AT: &str = "
 INSERT INTO river_queue(
     created_at,
     metadata,
@@ -1973,8 +2281,23 @@ INSERT INTO river_queue(
 SET
     updated_at = coalesce($4::timestamptz, now())
 RETURNING name, created_at, metadata, paused_at, updated_at
-",
-        )
+";
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct QueueCreateOrSetUpdatedAtInfo {
+    pub metadata: sqlx::types::Json<serde_json::Value>,
+    pub name: String,
+    pub paused_at: Option<chrono::DateTime<chrono::Utc>>,
+    pub updated_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+/// Generated from `river_queue.sql` (`:one`).
+pub async fn queue_create_or_set_updated_at<'e, E>(
+    db: E,
+    queue_create_or_set_updated_at_info: QueueCreateOrSetUpdatedAtInfo,
+) -> Result<RiverQueue, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: RiverQueue = sqlx::query_as(QUEUE_CREATE_OR_SET_UPDATED_AT)
         .bind(queue_create_or_set_updated_at_info.metadata)
         .bind(queue_create_or_set_updated_at_info.name)
         .bind(queue_create_or_set_updated_at_info.paused_at)
@@ -1983,24 +2306,25 @@ RETURNING name, created_at, metadata, paused_at, updated_at
         .await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct QueueDeleteExpiredRow {
-    pub name: String,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub metadata: sqlx::types::Json<serde_json::Value>,
-    pub paused_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub updated_at: chrono::DateTime<chrono::Utc>,
-}
-pub async fn queue_delete_expired<'e, E>(
+/// Generated from `river_queue.sql` (`:one`).
+pub async fn queue_create_or_set_updated_at_optional<'e, E>(
     db: E,
-    updated_at_horizon: chrono::DateTime<chrono::Utc>,
-    max: i64,
-) -> Result<Vec<QueueDeleteExpiredRow>, sqlx::Error>
+    queue_create_or_set_updated_at_info: QueueCreateOrSetUpdatedAtInfo,
+) -> Result<Option<RiverQueue>, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec: Vec<QueueDeleteExpiredRow> = sqlx::query_as(
-            "
+    let rec: Option<RiverQueue> = sqlx::query_as(QUEUE_CREATE_OR_SET_UPDATED_AT)
+        .bind(queue_create_or_set_updated_at_info.metadata)
+        .bind(queue_create_or_set_updated_at_info.name)
+        .bind(queue_create_or_set_updated_at_info.paused_at)
+        .bind(queue_create_or_set_updated_at_info.updated_at)
+        .fetch_optional(db)
+        .await?;
+    Ok(rec)
+}
+/// SQL of [`queue_delete_expired`].
+pub const QUEUE_DELETE_EXPIRED: &str = "
 DELETE FROM river_queue
 WHERE name IN (
     SELECT name
@@ -2010,83 +2334,95 @@ WHERE name IN (
     LIMIT $2::bigint
 )
 RETURNING name, created_at, metadata, paused_at, updated_at
-",
-        )
+";
+/// Generated from `river_queue.sql` (`:many`).
+pub async fn queue_delete_expired<'e, E>(
+    db: E,
+    updated_at_horizon: chrono::DateTime<chrono::Utc>,
+    max: i64,
+) -> Result<Vec<RiverQueue>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: Vec<RiverQueue> = sqlx::query_as(QUEUE_DELETE_EXPIRED)
         .bind(updated_at_horizon)
         .bind(max)
         .fetch_all(db)
         .await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct QueueGetRow {
-    pub name: String,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub metadata: sqlx::types::Json<serde_json::Value>,
-    pub paused_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub updated_at: chrono::DateTime<chrono::Utc>,
-}
-pub async fn queue_get<'e, E>(db: E, name: String) -> Result<QueueGetRow, sqlx::Error>
+/// Generated from `river_queue.sql` (`:many`).
+pub fn queue_delete_expired_stream<'e, E>(
+    db: E,
+    updated_at_horizon: chrono::DateTime<chrono::Utc>,
+    max: i64,
+) -> impl futures::Stream<Item = Result<RiverQueue, sqlx::Error>> + 'e
 where
-    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+    E: sqlx::Executor<'e, Database = sqlx::Postgres> + 'e,
 {
-    let rec: QueueGetRow = sqlx::query_as(
-            "
+    sqlx::query_as(QUEUE_DELETE_EXPIRED).bind(updated_at_horizon).bind(max).fetch(db)
+}
+/// SQL of [`queue_get`].
+pub const QUEUE_GET: &str = "
 SELECT name, created_at, metadata, paused_at, updated_at
 FROM river_queue
 WHERE name = $1::text
-",
-        )
-        .bind(name)
-        .fetch_one(db)
-        .await?;
+";
+/// Generated from `river_queue.sql` (`:one`).
+pub async fn queue_get<'e, E>(db: E, name: String) -> Result<RiverQueue, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: RiverQueue = sqlx::query_as(QUEUE_GET).bind(name).fetch_one(db).await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct QueueListRow {
-    pub name: String,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub metadata: sqlx::types::Json<serde_json::Value>,
-    pub paused_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub updated_at: chrono::DateTime<chrono::Utc>,
-}
-pub async fn queue_list<'e, E>(
+/// Generated from `river_queue.sql` (`:one`).
+pub async fn queue_get_optional<'e, E>(
     db: E,
-    limit_count: i32,
-) -> Result<Vec<QueueListRow>, sqlx::Error>
+    name: String,
+) -> Result<Option<RiverQueue>, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    let rec: Vec<QueueListRow> = sqlx::query_as(
-            "
+    let rec: Option<RiverQueue> = sqlx::query_as(QUEUE_GET)
+        .bind(name)
+        .fetch_optional(db)
+        .await?;
+    Ok(rec)
+}
+/// SQL of [`queue_list`].
+pub const QUEUE_LIST: &str = "
 SELECT name, created_at, metadata, paused_at, updated_at
 FROM river_queue
 ORDER BY name ASC
 LIMIT $1::integer
-",
-        )
+";
+/// Generated from `river_queue.sql` (`:many`).
+pub async fn queue_list<'e, E>(
+    db: E,
+    limit_count: i32,
+) -> Result<Vec<RiverQueue>, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    let rec: Vec<RiverQueue> = sqlx::query_as(QUEUE_LIST)
         .bind(limit_count)
         .fetch_all(db)
         .await?;
     Ok(rec)
 }
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct QueuePauseRow {
-    pub name: String,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub metadata: sqlx::types::Json<serde_json::Value>,
-    pub paused_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub updated_at: chrono::DateTime<chrono::Utc>,
-}
-pub async fn queue_pause<'e, E>(
+/// Generated from `river_queue.sql` (`:many`).
+pub fn queue_list_stream<'e, E>(
     db: E,
-    name: String,
-) -> Result<QueuePauseRow, sqlx::Error>
+    limit_count: i32,
+) -> impl futures::Stream<Item = Result<RiverQueue, sqlx::Error>> + 'e
 where
-    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+    E: sqlx::Executor<'e, Database = sqlx::Postgres> + 'e,
 {
-    sqlx::query(
-            "
+    sqlx::query_as(QUEUE_LIST).bind(limit_count).fetch(db)
+}
+/// SQL of [`queue_pause`].
+pub const QUEUE_PAUSE: &str = "
 WITH queue_to_update AS (
     SELECT name, paused_at
     FROM river_queue
@@ -2110,32 +2446,19 @@ WHERE name = $1
 UNION
 SELECT name, created_at, metadata, paused_at, updated_at
 FROM updated_queue
-",
-        )
-        .bind(name)
-        .execute(db)
-        .await?;
-    Err(sqlx::Error::TypeNotFound {
-        type_name: String::from(":execresult"),
-    })
-}
-#[derive(Debug, Clone, sqlx::FromRow)]
-pub struct QueueResumeRow {
-    pub name: String,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub metadata: sqlx::types::Json<serde_json::Value>,
-    pub paused_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub updated_at: chrono::DateTime<chrono::Utc>,
-}
-pub async fn queue_resume<'e, E>(
+";
+/// Generated from `river_queue.sql` (`:execresult`).
+pub async fn queue_pause<'e, E>(
     db: E,
     name: String,
-) -> Result<QueueResumeRow, sqlx::Error>
+) -> Result<sqlx::postgres::PgQueryResult, sqlx::Error>
 where
     E: sqlx::Executor<'e, Database = sqlx::Postgres>,
 {
-    sqlx::query(
-            "
+    sqlx::query(QUEUE_PAUSE).bind(name).execute(db).await
+}
+/// SQL of [`queue_resume`].
+pub const QUEUE_RESUME: &str = "
 WITH queue_to_update AS (
     SELECT name
     FROM river_queue
@@ -2158,12 +2481,45 @@ WHERE name = $1
 UNION
 SELECT name, created_at, metadata, paused_at, updated_at
 FROM updated_queue
-",
-        )
-        .bind(name)
-        .execute(db)
-        .await?;
-    Err(sqlx::Error::TypeNotFound {
-        type_name: String::from(":execresult"),
-    })
+";
+/// Generated from `river_queue.sql` (`:execresult`).
+pub async fn queue_resume<'e, E>(
+    db: E,
+    name: String,
+) -> Result<sqlx::postgres::PgQueryResult, sqlx::Error>
+where
+    E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+{
+    sqlx::query(QUEUE_RESUME).bind(name).execute(db).await
+}
+fn copy_in_field<'q, T>(
+    buf: &mut sqlx::postgres::PgArgumentBuffer,
+    value: T,
+) -> Result<(), sqlx::error::BoxDynError>
+where
+    T: sqlx::Encode<'q, sqlx::Postgres>,
+{
+    let offset = buf.len();
+    buf.extend_from_slice(&[0; 4]);
+    let len = match <T as sqlx::Encode<'q, sqlx::Postgres>>::encode(value, buf)? {
+        sqlx::encode::IsNull::No => i32::try_from(buf.len() - offset - 4)?,
+        sqlx::encode::IsNull::Yes => -1,
+    };
+    buf[offset..offset + 4].copy_from_slice(&len.to_be_bytes());
+    Ok(())
+}
+fn copy_in_array_field<'q, T>(
+    buf: &mut sqlx::postgres::PgArgumentBuffer,
+    value: T,
+    element_oid: u32,
+) -> Result<(), sqlx::error::BoxDynError>
+where
+    T: sqlx::Encode<'q, sqlx::Postgres>,
+{
+    let offset = buf.len();
+    copy_in_field(buf, value)?;
+    if buf.len() >= offset + 16 {
+        buf[offset + 12..offset + 16].copy_from_slice(&element_oid.to_be_bytes());
+    }
+    Ok(())
 }
@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+
+use heck::{ToKebabCase, ToShoutySnakeCase, ToSnakeCase};
 use proc_macro2::TokenStream;
 use quote::format_ident;
 use quote::ToTokens;
@@ -5,9 +8,467 @@ use quote::ToTokens;
 use crate::ident;
 use crate::plugin;
 
+/// Database driver the generated code is written against.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Driver {
+    Sqlx,
+    TokioPostgres,
+}
+
+impl Driver {
+    fn error_type(self) -> TokenStream {
+        match self {
+            Driver::Sqlx => quote::quote! { sqlx::Error },
+            Driver::TokioPostgres => quote::quote! { tokio_postgres::Error },
+        }
+    }
+}
+
+impl std::fmt::Display for Driver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Driver::Sqlx => f.write_str("sqlx"),
+            Driver::TokioPostgres => f.write_str("tokio-postgres"),
+        }
+    }
+}
+
+/// Database engine the queries were written for, taken from `Settings.engine`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Engine {
+    Postgresql,
+    Mysql,
+    Sqlite,
+}
+
+impl Engine {
+    fn sqlx_database(self) -> TokenStream {
+        match self {
+            Engine::Postgresql => quote::quote! { sqlx::Postgres },
+            Engine::Mysql => quote::quote! { sqlx::MySql },
+            Engine::Sqlite => quote::quote! { sqlx::Sqlite },
+        }
+    }
+
+    fn sqlx_connection(self) -> TokenStream {
+        match self {
+            Engine::Postgresql => quote::quote! { sqlx::PgConnection },
+            Engine::Mysql => quote::quote! { sqlx::MySqlConnection },
+            Engine::Sqlite => quote::quote! { sqlx::SqliteConnection },
+        }
+    }
+
+    fn sqlx_row(self) -> TokenStream {
+        match self {
+            Engine::Postgresql => quote::quote! { sqlx::postgres::PgRow },
+            Engine::Mysql => quote::quote! { sqlx::mysql::MySqlRow },
+            Engine::Sqlite => quote::quote! { sqlx::sqlite::SqliteRow },
+        }
+    }
+
+    fn sqlx_query_result(self) -> TokenStream {
+        match self {
+            Engine::Postgresql => quote::quote! { sqlx::postgres::PgQueryResult },
+            Engine::Mysql => quote::quote! { sqlx::mysql::MySqlQueryResult },
+            Engine::Sqlite => quote::quote! { sqlx::sqlite::SqliteQueryResult },
+        }
+    }
+
+    /// The type of the id returned by `:execlastid` and the query result method reading it.
+    fn sqlx_last_insert_id(self) -> Option<(TokenStream, TokenStream)> {
+        match self {
+            Engine::Postgresql => None,
+            Engine::Mysql => Some((quote::quote! { u64 }, quote::quote! { last_insert_id })),
+            Engine::Sqlite => Some((quote::quote! { i64 }, quote::quote! { last_insert_rowid })),
+        }
+    }
+}
+
+impl std::str::FromStr for Engine {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "postgresql" => Ok(Engine::Postgresql),
+            "mysql" => Ok(Engine::Mysql),
+            "sqlite" => Ok(Engine::Sqlite),
+            _ => Err(format!("unsupported engine: {s:?}")),
+        }
+    }
+}
+
+impl std::fmt::Display for Engine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Engine::Postgresql => f.write_str("postgresql"),
+            Engine::Mysql => f.write_str("mysql"),
+            Engine::Sqlite => f.write_str("sqlite"),
+        }
+    }
+}
+
+/// A Rust type replacing the generated one for a database type or a single column.
+///
+/// Exactly one of `db_type` and `column` is set. The replacement stands for the scalar type:
+/// nullable and array columns still wrap it in `Option` and `Vec`. A `db_type` written as an
+/// array, e.g. `int4[][]`, only matches arrays with that many dimensions and stands for the whole
+/// array, as does a `column` override of a multi-dimensional array with sqlx, which has no type
+/// for those.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct Override {
+    /// Database type name, e.g. `pg_catalog.numeric`, `numeric` or `numeric[][]`.
+    #[serde(default)]
+    pub db_type: Option<String>,
+    /// Column as `table.column` or `schema.table.column`.
+    #[serde(default)]
+    pub column: Option<String>,
+    /// Path of the Rust type to use, e.g. `sqlx::types::Json<JobArgs>`.
+    pub rust_type: String,
+    /// Whether a `db_type` override applies to nullable columns instead of non-null ones.
+    /// `column` overrides apply regardless.
+    #[serde(default)]
+    pub nullable: bool,
+}
+
+impl Override {
+    fn validate(&self) -> Result<(), String> {
+        match (&self.db_type, &self.column) {
+            (Some(_), None) | (None, Some(_)) => {}
+            _ => {
+                return Err(format!(
+                    "override {:?} must set exactly one of db_type and column",
+                    self.rust_type
+                ))
+            }
+        }
+        if let Some(column) = &self.column {
+            if !matches!(column.split('.').count(), 2 | 3) {
+                return Err(format!(
+                    "override column {column:?} must be table.column or schema.table.column"
+                ));
+            }
+        }
+        syn::parse_str::<syn::Type>(&self.rust_type)
+            .map_err(|e| format!("override rust_type {:?} is not a type: {e}", self.rust_type))?;
+        Ok(())
+    }
+
+    fn rust_type(&self) -> TokenStream {
+        syn::parse_str::<syn::Type>(&self.rust_type)
+            .expect("override rust_type should be validated")
+            .to_token_stream()
+    }
+
+    /// Whether the override is for an array `db_type` and replaces the whole array.
+    fn is_array_db_type(&self) -> bool {
+        self.db_type
+            .as_deref()
+            .is_some_and(|db_type| db_type.ends_with("[]"))
+    }
+
+    fn matches_db_type(&self, type_: &plugin::Identifier, not_null: bool, dims: i32) -> bool {
+        let Some(db_type) = self.db_type.as_deref() else {
+            return false;
+        };
+        if self.nullable == not_null {
+            return false;
+        }
+        let element = db_type.trim_end_matches("[]");
+        let override_dims = (db_type.len() - element.len()) / 2;
+        if override_dims > 0 && usize::try_from(dims).ok() != Some(override_dims) {
+            return false;
+        }
+        let unqualified = |name: &str| name.strip_prefix("pg_catalog.").unwrap_or(name).to_string();
+        let qualified = if type_.schema.is_empty() {
+            type_.name.clone()
+        } else {
+            format!("{}.{}", type_.schema, type_.name)
+        };
+        element == qualified || unqualified(element) == unqualified(&type_.name)
+    }
+
+    fn matches_column(&self, col: &plugin::Column, default_schema: &str) -> bool {
+        let (Some(column), Some(table)) = (self.column.as_deref(), col.table.as_ref()) else {
+            return false;
+        };
+        let col_name = if col.original_name.is_empty() {
+            &col.name
+        } else {
+            &col.original_name
+        };
+        let table_schema = if table.schema.is_empty() {
+            default_schema
+        } else {
+            &table.schema
+        };
+        match column.split('.').collect::<Vec<_>>().as_slice() {
+            [t, c] => *t == table.name && c == col_name,
+            [s, t, c] => *s == table_schema && *t == table.name && c == col_name,
+            _ => false,
+        }
+    }
+}
+
+/// Identifiers replacing generated ones, one map per kind of item so a name shared by a table
+/// and a column, or a column and an enum, only renames the intended one. Keys are SQL names or
+/// generated Rust names.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct Rename {
+    /// Structs and enums, keyed by table, enum or composite type name (`schema.name` outside the
+    /// default schema), or by generated struct name like `JobGetByIdRow`.
+    pub types: HashMap<String, String>,
+    /// Struct fields, keyed by column name.
+    pub columns: HashMap<String, String>,
+    /// Enum variants, keyed by `enum.value` or value.
+    pub values: HashMap<String, String>,
+    /// Functions, keyed by query name.
+    pub queries: HashMap<String, String>,
+}
+
+impl Rename {
+    fn validate(&self) -> Result<(), String> {
+        let sections = [
+            ("types", &self.types),
+            ("columns", &self.columns),
+            ("values", &self.values),
+            ("queries", &self.queries),
+        ];
+        for (section, map) in sections {
+            for (from, to) in map {
+                syn::parse_str::<syn::Ident>(to).map_err(|e| {
+                    format!("rename {section} {from:?} to {to:?} is not an identifier: {e}")
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The configured identifier for the first matching key, falling back to the generated one.
+    fn apply(map: &HashMap<String, String>, keys: &[&str], generated: String) -> String {
+        let renamed = keys
+            .iter()
+            .copied()
+            .chain([generated.as_str()])
+            .find_map(|key| map.get(key).cloned());
+        renamed.unwrap_or(generated)
+    }
+
+    fn type_(&self, sql_name: &str, generated: String) -> String {
+        Self::apply(&self.types, &[sql_name], generated)
+    }
+
+    fn column(&self, sql_name: &str, generated: String) -> String {
+        Self::apply(&self.columns, &[sql_name], generated)
+    }
+
+    fn value(&self, enum_name: &str, value: &str, generated: String) -> String {
+        Self::apply(
+            &self.values,
+            &[&format!("{enum_name}.{value}"), value],
+            generated,
+        )
+    }
+
+    fn query(&self, sql_name: &str, generated: String) -> String {
+        Self::apply(&self.queries, &[sql_name], generated)
+    }
+}
+
+/// Fields of a PostgreSQL composite type, which sqlc does not pass to plugins.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct CompositeType {
+    /// Type name, e.g. `point3` or `schema.point3` outside of the default schema.
+    pub name: String,
+    /// Attributes in declaration order, the order records are decoded in.
+    pub fields: Vec<CompositeField>,
+}
+
+/// An attribute of a composite type.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+pub struct CompositeField {
+    pub name: String,
+    /// Database type name, e.g. `int4`, `pg_catalog.numeric` or `mood[]` for an array.
+    pub db_type: String,
+    #[serde(default)]
+    pub not_null: bool,
+}
+
+impl CompositeType {
+    fn matches(&self, schema: &str, name: &str, default_schema: &str) -> bool {
+        match self.name.split_once('.') {
+            Some((s, n)) => s == schema && n == name,
+            None => (schema.is_empty() || schema == default_schema) && self.name == name,
+        }
+    }
+
+    fn columns(&self) -> Vec<plugin::Column> {
+        self.fields
+            .iter()
+            .map(|field| {
+                let element = field.db_type.trim_end_matches("[]");
+                let dims = (field.db_type.len() - element.len()) / 2;
+                let (schema, name) = element.split_once('.').unwrap_or(("", element));
+                plugin::Column {
+                    name: field.name.clone(),
+                    not_null: field.not_null,
+                    is_array: dims > 0,
+                    array_dims: i32::try_from(dims).unwrap_or(i32::MAX),
+                    r#type: Some(plugin::Identifier {
+                        schema: schema.to_string(),
+                        name: name.to_string(),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }
+            })
+            .collect()
+    }
+}
+
+/// Crate providing date and time types.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum TimeCrate {
+    #[default]
+    Chrono,
+    Time,
+}
+
+/// Time zone of the `chrono::DateTime` a PostgreSQL `timestamptz` decodes to.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum ChronoTimestamptz {
+    #[default]
+    Utc,
+    FixedOffset,
+}
+
+/// Crate providing exact decimal types.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DecimalCrate {
+    #[default]
+    RustDecimal,
+    Bigdecimal,
+}
+
+/// Choices between crates providing the same SQL types.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct TypeOptions {
+    pub time_crate: TimeCrate,
+    pub chrono_timestamptz: ChronoTimestamptz,
+    pub decimal: DecimalCrate,
+}
+
+impl TypeOptions {
+    fn validate(&self, driver: Driver) -> Result<(), String> {
+        if driver == Driver::TokioPostgres && self.decimal == DecimalCrate::Bigdecimal {
+            return Err("decimal \"bigdecimal\" is not supported by tokio-postgres".to_string());
+        }
+        Ok(())
+    }
+
+    fn decimal(&self) -> TokenStream {
+        match self.decimal {
+            DecimalCrate::RustDecimal => quote::quote! { rust_decimal::Decimal },
+            DecimalCrate::Bigdecimal => quote::quote! { sqlx::types::BigDecimal },
+        }
+    }
+
+    fn date(&self) -> TokenStream {
+        match self.time_crate {
+            TimeCrate::Chrono => quote::quote! { chrono::NaiveDate },
+            TimeCrate::Time => quote::quote! { time::Date },
+        }
+    }
+
+    fn time(&self) -> TokenStream {
+        match self.time_crate {
+            TimeCrate::Chrono => quote::quote! { chrono::NaiveTime },
+            TimeCrate::Time => quote::quote! { time::Time },
+        }
+    }
+
+    fn timestamp(&self) -> TokenStream {
+        match self.time_crate {
+            TimeCrate::Chrono => quote::quote! { chrono::NaiveDateTime },
+            TimeCrate::Time => quote::quote! { time::PrimitiveDateTime },
+        }
+    }
+
+    fn timestamptz(&self) -> TokenStream {
+        match (self.time_crate, self.chrono_timestamptz) {
+            (TimeCrate::Chrono, ChronoTimestamptz::Utc) => {
+                quote::quote! { chrono::DateTime<chrono::Utc> }
+            }
+            (TimeCrate::Chrono, ChronoTimestamptz::FixedOffset) => {
+                quote::quote! { chrono::DateTime<chrono::FixedOffset> }
+            }
+            (TimeCrate::Time, _) => quote::quote! { time::OffsetDateTime },
+        }
+    }
+
+    /// MySQL `TIMESTAMP`, which sqlx only decodes to a UTC `chrono::DateTime`.
+    fn mysql_timestamp(&self) -> TokenStream {
+        match self.time_crate {
+            TimeCrate::Chrono => quote::quote! { chrono::DateTime<chrono::Utc> },
+            TimeCrate::Time => quote::quote! { time::OffsetDateTime },
+        }
+    }
+
+    /// PostgreSQL `timetz`, which only sqlx decodes.
+    fn timetz(&self) -> TokenStream {
+        match self.time_crate {
+            TimeCrate::Chrono => quote::quote! {
+                sqlx::postgres::types::PgTimeTz<chrono::NaiveTime, chrono::FixedOffset>
+            },
+            TimeCrate::Time => quote::quote! {
+                sqlx::postgres::types::PgTimeTz<time::Time, time::UtcOffset>
+            },
+        }
+    }
+}
+
+/// Names and layout of the generated files.
+#[derive(serde::Serialize, serde::Deserialize, Debug, Clone)]
+#[serde(default)]
+pub struct Output {
+    /// Write one module per SQL file, plus a models module and a module file, instead of a single
+    /// file.
+    #[serde(rename = "output_split_files")]
+    pub split_files: bool,
+    /// Name of the single generated file.
+    #[serde(rename = "output_queries_file_name")]
+    pub queries_file_name: String,
+    /// Name of the file holding enums, composite types and table models when splitting files.
+    #[serde(rename = "output_models_file_name")]
+    pub models_file_name: String,
+    /// Name of the file declaring and re-exporting every module when splitting files.
+    #[serde(rename = "output_mod_file_name")]
+    pub mod_file_name: String,
+    /// Appended to the name of each SQL file to name its module when splitting files.
+    #[serde(rename = "output_files_suffix")]
+    pub files_suffix: String,
+}
+
+impl Default for Output {
+    fn default() -> Self {
+        Self {
+            split_files: false,
+            queries_file_name: "queries.rs".to_string(),
+            models_file_name: "models.rs".to_string(),
+            mod_file_name: "mod.rs".to_string(),
+            files_suffix: String::new(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 enum Params {
-    DBType(Vec<plugin::Column>),
+    DBType(Vec<GenField>),
     Struct { name: String, type_: String },
     None,
 }
@@ -17,12 +478,16 @@ impl quote::ToTokens for Params {
         match self {
             Params::DBType(params) => {
                 eprintln!("Params::ToTokens: {self:?}");
-                let param_tokens = params.iter().map(|col| {
-                    let field_name = format_ident!("{}", ident::to_snake(col.name.as_str()));
-                    let field_type = convert_postgres_type(col);
-                    quote::quote! { #field_name: #field_type }
+                let params = params.iter().map(|field| {
+                    let field_name = field.name();
+                    let field_type = &field.type_;
+                    if field.col.is_sqlc_slice {
+                        quote::quote! { #field_name: &[#field_type] }
+                    } else {
+                        quote::quote! { #field_name: #field_type }
+                    }
                 });
-                quote::quote! { #(#param_tokens),* }.to_tokens(tokens);
+                quote::quote! { #(#params),* }.to_tokens(tokens);
             }
             Params::Struct { name, type_ } => {
                 eprintln!("Params::ToTokens: {self:?}");
@@ -35,38 +500,289 @@ impl quote::ToTokens for Params {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 struct GenField {
+    name: String,
     col: plugin::Column,
+    type_: TokenStream,
+    /// Fields of the model a `sqlc.embed()` field holds.
+    embed: Option<Vec<GenField>>,
+}
+
+impl GenField {
+    fn name(&self) -> proc_macro2::Ident {
+        format_ident!("{}", self.name.as_str())
+    }
+
+    /// `#[sqlx(rename = ...)]` when the field name no longer matches the column it decodes.
+    fn sqlx_attr(&self) -> Option<TokenStream> {
+        let col_name = self.col.name.as_str();
+        (self.name.trim_start_matches("r#") != col_name)
+            .then(|| quote::quote! { #[sqlx(rename = #col_name)] })
+    }
 }
 
+/// Tokens for a public struct field, documented by its column comment.
 impl quote::ToTokens for GenField {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         eprintln!("GenField::ToTokens: {self:?}");
-        let field_name = format_ident!("{}", ident::to_snake(self.col.name.as_str()));
-        let field_type = convert_postgres_type(&self.col);
-        quote::quote! { #field_name: #field_type }.to_tokens(tokens);
+        let doc = doc_tokens(&self.col.comment);
+        let field_name = self.name();
+        let field_type = &self.type_;
+        if self.col.is_sqlc_slice {
+            quote::quote! { #doc pub #field_name: Vec<#field_type> }.to_tokens(tokens);
+        } else {
+            quote::quote! { #doc pub #field_name: #field_type }.to_tokens(tokens);
+        }
     }
 }
 
 #[derive(Debug)]
 pub struct GenStruct {
     name: String,
+    comment: String,
     fields: Vec<GenField>,
     cols: Vec<plugin::Column>,
+    driver: Driver,
+    engine: Engine,
+}
+
+impl GenStruct {
+    /// Decodes each field by its column index, embedded models spanning one column per field.
+    /// Names are ambiguous once the embedded tables share a column like `id`.
+    fn indexed_values(&self) -> Vec<TokenStream> {
+        let mut index = 0usize;
+        let mut next = || {
+            let literal = proc_macro2::Literal::usize_unsuffixed(index);
+            index += 1;
+            quote::quote! { row.try_get(#literal)? }
+        };
+        self.fields
+            .iter()
+            .map(|field| match &field.embed {
+                Some(embed_fields) => {
+                    let type_ = &field.type_;
+                    let names = embed_fields.iter().map(GenField::name);
+                    let values: Vec<_> = embed_fields.iter().map(|_| next()).collect();
+                    quote::quote! { #type_ { #(#names: #values),* } }
+                }
+                None => next(),
+            })
+            .collect()
+    }
 }
 
-impl GenStruct {}
 impl quote::ToTokens for GenStruct {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         eprintln!("GenStruct::ToTokens: {self:?}");
         let fields = self.fields.as_slice();
+        let doc = doc_tokens(&self.comment);
 
         let struct_name = format_ident!("{}", self.name.as_str());
-        quote::quote! {
-            #[derive(Debug, Clone, sqlx::FromRow)]
-            pub struct #struct_name {
-                #(pub #fields),*
+        let has_embed = fields.iter().any(|field| field.embed.is_some());
+        match self.driver {
+            Driver::Sqlx if has_embed => {
+                let row = self.engine.sqlx_row();
+                let field_names = fields.iter().map(GenField::name);
+                let field_values = self.indexed_values();
+                quote::quote! {
+                    #doc
+                    #[derive(Debug, Clone)]
+                    pub struct #struct_name {
+                        #(#fields),*
+                    }
+
+                    impl sqlx::FromRow<'_, #row> for #struct_name {
+                        fn from_row(row: &#row) -> Result<Self, sqlx::Error> {
+                            use sqlx::Row as _;
+                            Ok(Self {
+                                #(#field_names: #field_values),*
+                            })
+                        }
+                    }
+                }
+            }
+            Driver::Sqlx => {
+                let attrs = fields.iter().map(GenField::sqlx_attr);
+                quote::quote! {
+                    #doc
+                    #[derive(Debug, Clone, sqlx::FromRow)]
+                    pub struct #struct_name {
+                        #(#attrs #fields),*
+                    }
+                }
+            }
+            Driver::TokioPostgres => {
+                let field_names = fields.iter().map(GenField::name);
+                let field_values: Vec<_> = if has_embed {
+                    self.indexed_values()
+                } else {
+                    fields
+                        .iter()
+                        .map(|field| {
+                            let col_name = field.col.name.as_str();
+                            quote::quote! { row.try_get(#col_name)? }
+                        })
+                        .collect()
+                };
+                quote::quote! {
+                    #doc
+                    #[derive(Debug, Clone)]
+                    pub struct #struct_name {
+                        #(#fields),*
+                    }
+
+                    impl TryFrom<&tokio_postgres::Row> for #struct_name {
+                        type Error = tokio_postgres::Error;
+
+                        fn try_from(row: &tokio_postgres::Row) -> Result<Self, Self::Error> {
+                            Ok(Self {
+                                #(#field_names: #field_values),*
+                            })
+                        }
+                    }
+                }
+            }
+        }
+        .to_tokens(tokens);
+    }
+}
+
+/// The first `rename_all` strategy understood by the `sqlx::Type` derive that maps every variant
+/// back to its SQL value.
+fn enum_rename_all(variants: &[(String, String)]) -> Option<&'static str> {
+    [
+        "lowercase",
+        "snake_case",
+        "UPPERCASE",
+        "SCREAMING_SNAKE_CASE",
+        "kebab-case",
+    ]
+    .into_iter()
+    .find(|strategy| {
+        variants.iter().all(|(variant, value)| {
+            let renamed = match *strategy {
+                "lowercase" => variant.to_lowercase(),
+                "snake_case" => variant.to_snake_case(),
+                "UPPERCASE" => variant.to_uppercase(),
+                "SCREAMING_SNAKE_CASE" => variant.to_shouty_snake_case(),
+                _ => variant.to_kebab_case(),
+            };
+            renamed == *value
+        })
+    })
+}
+
+#[derive(Debug)]
+pub struct GenEnum {
+    name: String,
+    comment: String,
+    type_name: String,
+    /// Rust variant names paired with the SQL values they stand for.
+    variants: Vec<(String, String)>,
+    driver: Driver,
+    engine: Engine,
+}
+
+impl quote::ToTokens for GenEnum {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let doc = doc_tokens(&self.comment);
+        let enum_name = format_ident!("{}", self.name.as_str());
+        let variant_names: Vec<_> = self
+            .variants
+            .iter()
+            .map(|(variant, _)| format_ident!("{}", variant.as_str()))
+            .collect();
+        let values = self.variants.iter().map(|(_, value)| value.as_str());
+        match self.driver {
+            Driver::Sqlx => {
+                let rename_all = enum_rename_all(&self.variants);
+                let mut container = Vec::new();
+                if self.engine == Engine::Postgresql {
+                    let type_name = self.type_name.as_str();
+                    container.push(quote::quote! { type_name = #type_name });
+                }
+                if let Some(rename_all) = rename_all {
+                    container.push(quote::quote! { rename_all = #rename_all });
+                }
+                let container = (!container.is_empty()).then(|| {
+                    quote::quote! { #[sqlx(#(#container),*)] }
+                });
+                let variant_attrs = values.map(|value| {
+                    rename_all
+                        .is_none()
+                        .then(|| quote::quote! { #[sqlx(rename = #value)] })
+                });
+                quote::quote! {
+                    #doc
+                    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, sqlx::Type)]
+                    #container
+                    pub enum #enum_name {
+                        #(#variant_attrs #variant_names),*
+                    }
+                }
+            }
+            Driver::TokioPostgres => {
+                let type_name = self.type_name.as_str();
+                quote::quote! {
+                    #doc
+                    #[derive(
+                        Debug,
+                        Clone,
+                        Copy,
+                        PartialEq,
+                        Eq,
+                        Hash,
+                        postgres_types::ToSql,
+                        postgres_types::FromSql,
+                    )]
+                    #[postgres(name = #type_name)]
+                    pub enum #enum_name {
+                        #(#[postgres(name = #values)] #variant_names),*
+                    }
+                }
+            }
+        }
+        .to_tokens(tokens);
+    }
+}
+
+/// A PostgreSQL composite type, decoded as a record whose fields are matched in order.
+#[derive(Debug)]
+pub struct GenComposite {
+    name: String,
+    comment: String,
+    type_name: String,
+    fields: Vec<GenField>,
+    driver: Driver,
+}
+
+impl quote::ToTokens for GenComposite {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let doc = doc_tokens(&self.comment);
+        let struct_name = format_ident!("{}", self.name.as_str());
+        let type_name = self.type_name.as_str();
+        let fields = self.fields.as_slice();
+        match self.driver {
+            // The derive also implements `PgHasArrayType` using `PgTypeInfo::array_of`.
+            Driver::Sqlx => quote::quote! {
+                #doc
+                #[derive(Debug, Clone, sqlx::Type)]
+                #[sqlx(type_name = #type_name)]
+                pub struct #struct_name {
+                    #(#fields),*
+                }
+            },
+            Driver::TokioPostgres => {
+                let col_names = fields.iter().map(|field| field.col.name.as_str());
+                quote::quote! {
+                    #doc
+                    #[derive(Debug, Clone, postgres_types::ToSql, postgres_types::FromSql)]
+                    #[postgres(name = #type_name)]
+                    pub struct #struct_name {
+                        #(#[postgres(name = #col_names)] #fields),*
+                    }
+                }
             }
         }
         .to_tokens(tokens);
@@ -75,123 +791,665 @@ impl quote::ToTokens for GenStruct {
 
 struct GenQuery<'query> {
     query: &'query plugin::Query,
+    func_name: String,
     structs: Vec<&'query GenStruct>,
     params: Params,
     return_: TokenStream,
+    row: TokenStream,
+    driver: Driver,
+    engine: Engine,
 }
 
-impl<'query> quote::ToTokens for GenQuery<'query> {
-    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
-        eprintln!("query::ToTokens: {:?}", self.query.name);
-        let func_name = quote::format_ident!("{}", ident::to_snake(self.query.name.as_str()));
-        let sql = format!("\n{}\n", self.query.text.as_str());
-
-        let cmd = self.query.cmd.as_str();
-        let params = self.params.clone();
-        let structs_ = self.structs.as_slice();
-        let return_tokens = self.return_.clone();
-
-        let query_cols: Vec<plugin::Column> = self
-            .query
+impl GenQuery<'_> {
+    fn query_cols(&self) -> Vec<plugin::Column> {
+        self.query
             .columns
             .iter()
             .filter_map(|c| match c.r#type {
                 Some(ref t) if t.name == "void" => None,
                 _ => Some(c.clone()),
             })
-            .collect();
+            .collect()
+    }
+
+    /// Name of the public constant holding the query's SQL.
+    fn sql_const(&self) -> proc_macro2::Ident {
+        format_ident!(
+            "{}",
+            ident::to_shouty_snake(self.func_name.trim_start_matches("r#"))
+        )
+    }
+
+    fn sql_const_tokens(&self) -> TokenStream {
+        let name = self.sql_const();
+        let doc = format!(" SQL of [`{}`].", self.func_name.trim_start_matches("r#"));
+        let sql = format!("\n{}\n", self.query.text.as_str());
+        quote::quote! {
+            #[doc = #doc]
+            pub const #name: &str = #sql;
+        }
+    }
+
+    /// Doc comment of the generated functions: the comments above the query followed by where it
+    /// comes from.
+    fn doc_tokens(&self) -> TokenStream {
+        let mut comments = self.query.comments.clone();
+        while comments.last().is_some_and(|line| line.trim().is_empty()) {
+            comments.pop();
+        }
+        if !comments.is_empty() {
+            comments.push(String::new());
+        }
+        comments.push(format!(
+            " Generated from `{}` (`{}`).",
+            self.query.filename, self.query.cmd
+        ));
+        doc_tokens(&comments.join("\n"))
+    }
+
+    /// Whether rows are a single value rather than a struct.
+    fn is_scalar(&self) -> bool {
+        matches!(self.query_cols().as_slice(), [col] if !is_embed(col))
+    }
 
-        let params_bind_tokens = match &params {
-            Params::DBType(params) => params
+    /// Fields holding the query parameters, in bind order.
+    fn param_fields(&self) -> Vec<&GenField> {
+        match &self.params {
+            Params::DBType(params) => params.iter().collect(),
+            Params::Struct { type_, .. } => self
+                .structs
+                .iter()
+                .find(|struct_| struct_.name.as_str() == type_)
+                .expect("struct should exist")
+                .fields
                 .iter()
-                .map(|col| {
-                    let field_name = format_ident!("{}", ident::to_snake(col.name.as_str()));
-                    quote::quote! { .bind(#field_name) }
-                })
                 .collect(),
-            Params::Struct { name, type_ } => {
-                let struct_name = format_ident!("{}", ident::to_snake(name));
-                let struct_ = structs_
-                    .iter()
-                    .find(|struct_| struct_.name.as_str() == type_)
-                    .expect("struct should exist");
-                struct_
-                    .fields
-                    .iter()
-                    .map(|field| {
-                        let field_name =
-                            format_ident!("{}", ident::to_snake(field.col.name.as_str()));
-                        quote::quote! { .bind(#struct_name.#field_name) }.to_token_stream()
-                    })
-                    .collect()
-            }
             Params::None => vec![],
-        };
+        }
+    }
+
+    /// Expressions referencing every query parameter, in bind order.
+    fn param_exprs(&self) -> Vec<TokenStream> {
+        let struct_name = match &self.params {
+            Params::Struct { name, .. } => Some(format_ident!("{}", ident::to_snake(name))),
+            _ => None,
+        };
+        self.param_fields()
+            .into_iter()
+            .map(|field| {
+                let field_name = field.name();
+                match &struct_name {
+                    Some(struct_name) => quote::quote! { #struct_name.#field_name },
+                    None => field_name.to_token_stream(),
+                }
+            })
+            .collect()
+    }
+
+    /// Whether a `sqlc.slice()` parameter requires expanding the SQL at call time.
+    fn has_slices(&self) -> bool {
+        self.param_fields()
+            .iter()
+            .any(|field| field.col.is_sqlc_slice)
+    }
+
+    /// Whether a `:copyfrom` query has array columns whose element type must be patched.
+    fn copies_patched_arrays(&self) -> bool {
+        self.query.cmd == ":copyfrom"
+            && self
+                .param_fields()
+                .iter()
+                .any(|field| copy_in_patched_oid(&field.col).is_some())
+    }
+
+    /// The `COPY ... FROM STDIN` statement equivalent to a `:copyfrom` insert.
+    fn copy_in_statement(&self) -> String {
+        let table = self
+            .query
+            .insert_into_table
+            .as_ref()
+            .expect(":copyfrom query should insert into a table");
+        let table_name = if table.schema.is_empty() {
+            format!("\"{}\"", table.name)
+        } else {
+            format!("\"{}\".\"{}\"", table.schema, table.name)
+        };
+        let columns: Vec<String> = self
+            .query
+            .params
+            .iter()
+            .filter_map(|param| param.column.as_ref())
+            .map(|col| format!("\"{}\"", col.name))
+            .collect();
+        format!(
+            "COPY {table_name} ({}) FROM STDIN (FORMAT binary)",
+            columns.join(", ")
+        )
+    }
+
+    /// The item type of a query taking many parameter sets, and the expressions reading each
+    /// parameter from an item bound to `binding`.
+    fn params_item(&self, binding: &str) -> (TokenStream, Vec<TokenStream>) {
+        let binding = format_ident!("{}", binding);
+        match &self.params {
+            Params::Struct { type_, .. } => {
+                let struct_ = self
+                    .structs
+                    .iter()
+                    .find(|struct_| struct_.name.as_str() == type_)
+                    .expect("struct should exist");
+                let field_exprs = struct_
+                    .fields
+                    .iter()
+                    .map(|field| {
+                        let field_name = field.name();
+                        quote::quote! { #binding.#field_name }
+                    })
+                    .collect();
+                (format_ident!("{}", type_).to_token_stream(), field_exprs)
+            }
+            Params::None => (quote::quote! { () }, vec![]),
+            Params::DBType(_) => panic!("{} should take a params struct", self.query.cmd),
+        }
+    }
+
+    fn sqlx_copyfrom_tokens(&self) -> TokenStream {
+        let func_name = format_ident!("{}", self.func_name.as_str());
+        let doc = self.doc_tokens();
+        let copy_sql = self.copy_in_statement();
+        let structs_ = self.structs.as_slice();
+        let (row_type, field_exprs) = self.params_item("row");
+        let field_count = i16::try_from(field_exprs.len()).expect("too many :copyfrom columns");
+        let field_copies =
+            self.param_fields()
+                .into_iter()
+                .zip(&field_exprs)
+                .map(|(field, expr)| {
+                    if let Some(oid) = copy_in_patched_oid(&field.col) {
+                        quote::quote! { copy_in_array_field(&mut buf, #expr, #oid) }
+                    } else {
+                        quote::quote! { copy_in_field(&mut buf, #expr) }
+                    }
+                });
+        let header: TokenStream =
+            "b\"PGCOPY\\x0a\\xff\\x0d\\x0a\\x00\\x00\\x00\\x00\\x00\\x00\\x00\\x00\\x00\""
+                .parse()
+                .expect("header should be a valid byte string");
+
+        quote::quote! {
+            #(#structs_)*
+
+            #doc
+
+            pub async fn #func_name(
+                db: &mut sqlx::PgConnection,
+                rows: impl IntoIterator<Item = #row_type>,
+            ) -> Result<u64, sqlx::Error> {
+                let mut copy = db.copy_in_raw(#copy_sql).await?;
+                let mut buf = sqlx::postgres::PgArgumentBuffer::default();
+                buf.extend_from_slice(#header);
+                for row in rows {
+                    buf.extend_from_slice(&#field_count.to_be_bytes());
+                    let encoded = Ok(())
+                        #(.and_then(|()| #field_copies))*;
+                    if let Err(err) = encoded {
+                        copy.abort(err.to_string()).await?;
+                        return Err(sqlx::Error::Encode(err));
+                    }
+                    if buf.len() >= 65_536 {
+                        copy.send(buf.as_slice()).await?;
+                        buf.clear();
+                    }
+                }
+                buf.extend_from_slice(&(-1_i16).to_be_bytes());
+                copy.send(buf.as_slice()).await?;
+                copy.finish().await
+            }
+        }
+    }
+
+    fn tokio_postgres_copyfrom_tokens(&self) -> TokenStream {
+        let func_name = format_ident!("{}", self.func_name.as_str());
+        let doc = self.doc_tokens();
+        let sql = self.sql_const();
+        let copy_sql = self.copy_in_statement();
+        let structs_ = self.structs.as_slice();
+        let (row_type, field_exprs) = self.params_item("row");
+
+        quote::quote! {
+            #(#structs_)*
+
+            #doc
+
+            pub async fn #func_name(
+                client: &impl tokio_postgres::GenericClient,
+                rows: impl IntoIterator<Item = #row_type>,
+            ) -> Result<u64, tokio_postgres::Error> {
+                let statement = client.prepare(#sql).await?;
+                let sink = client.client().copy_in(#copy_sql).await?;
+                let writer =
+                    tokio_postgres::binary_copy::BinaryCopyInWriter::new(sink, statement.params());
+                let mut writer = std::pin::pin!(writer);
+                for row in rows {
+                    writer.as_mut().write(&[#(&#field_exprs),*]).await?;
+                }
+                writer.finish().await
+            }
+        }
+    }
+
+    fn sqlx_batch_tokens(&self) -> TokenStream {
+        let func_name = format_ident!("{}", self.func_name.as_str());
+        let doc = self.doc_tokens();
+        let sql = self.sql_const();
+        let structs_ = self.structs.as_slice();
+        let return_tokens = &self.return_;
+        let row_tokens = &self.row;
+        let connection = self.engine.sqlx_connection();
+        let (item_type, param_exprs) = self.params_item("item");
+        let item_pat = if param_exprs.is_empty() {
+            quote::quote! { () }
+        } else {
+            quote::quote! { item }
+        };
+        let single_col = self.is_scalar();
+
+        let query_func_tokens = match self.query.cmd.as_str() {
+            ":batchexec" => quote::quote! { query },
+            _ if single_col => quote::quote! { query_scalar },
+            _ => quote::quote! { query_as },
+        };
+        let result_tokens = match self.query.cmd.as_str() {
+            ":batchexec" => quote::quote! { .execute(&mut *db).await.map(|_| ()) },
+            ":batchone" => quote::quote! { .fetch_one(&mut *db).await },
+            ":batchmany" => quote::quote! { .fetch_all(&mut *db).await },
+            _ => panic!("unknown batch command: {}", self.query.cmd),
+        };
+        let item_result_type = match self.query.cmd.as_str() {
+            ":batchone" => quote::quote! { Result<#row_tokens, sqlx::Error> },
+            ":batchmany" => quote::quote! { Result<Vec<#row_tokens>, sqlx::Error> },
+            _ => quote::quote! { Result<(), sqlx::Error> },
+        };
+
+        quote::quote! {
+            #(#structs_)*
+
+            #doc
+
+            pub async fn #func_name(
+                db: &mut #connection,
+                batch: impl IntoIterator<Item = #item_type>,
+            ) -> Result<#return_tokens, sqlx::Error> {
+                sqlx::Executor::prepare(&mut *db, #sql).await?;
+                let mut results = Vec::new();
+                for #item_pat in batch {
+                    let result: #item_result_type = sqlx::#query_func_tokens(#sql)
+                    #(.bind(#param_exprs))*
+                    #result_tokens;
+                    results.push(result);
+                }
+
+                Ok(results)
+            }
+        }
+    }
+
+    fn tokio_postgres_batch_tokens(&self) -> TokenStream {
+        let func_name = format_ident!("{}", self.func_name.as_str());
+        let doc = self.doc_tokens();
+        let sql = self.sql_const();
+        let structs_ = self.structs.as_slice();
+        let return_tokens = &self.return_;
+        let row_tokens = &self.row;
+        let (item_type, param_exprs) = self.params_item("item");
+        let item_pat = if param_exprs.is_empty() {
+            quote::quote! { () }
+        } else {
+            quote::quote! { item }
+        };
+        let single_col = self.is_scalar();
+
+        let result_tokens = match self.query.cmd.as_str() {
+            ":batchexec" => quote::quote! {
+                client.execute(statement, &[#(&#param_exprs),*]).await.map(|_| ())
+            },
+            ":batchone" if single_col => quote::quote! {
+                client
+                    .query_one(statement, &[#(&#param_exprs),*])
+                    .await
+                    .and_then(|row| row.try_get(0))
+            },
+            ":batchone" => quote::quote! {
+                client
+                    .query_one(statement, &[#(&#param_exprs),*])
+                    .await
+                    .and_then(|row| #row_tokens::try_from(&row))
+            },
+            ":batchmany" if single_col => quote::quote! {
+                client
+                    .query(statement, &[#(&#param_exprs),*])
+                    .await
+                    .and_then(|rows| rows.iter().map(|row| row.try_get(0)).collect())
+            },
+            ":batchmany" => quote::quote! {
+                client
+                    .query(statement, &[#(&#param_exprs),*])
+                    .await
+                    .and_then(|rows| rows.iter().map(#row_tokens::try_from).collect())
+            },
+            _ => panic!("unknown batch command: {}", self.query.cmd),
+        };
+
+        quote::quote! {
+            #(#structs_)*
+
+            #doc
+
+            pub async fn #func_name(
+                client: &impl tokio_postgres::GenericClient,
+                batch: impl IntoIterator<Item = #item_type>,
+            ) -> Result<#return_tokens, tokio_postgres::Error> {
+                let statement = &client.prepare(#sql).await?;
+                let results = futures::future::join_all(batch.into_iter().map(|#item_pat| async move {
+                    #result_tokens
+                }))
+                .await;
+
+                Ok(results)
+            }
+        }
+    }
+
+    fn tokio_postgres_tokens(&self) -> TokenStream {
+        let func_name = format_ident!("{}", self.func_name.as_str());
+        let doc = self.doc_tokens();
+        let sql = self.sql_const();
+
+        let params = &self.params;
+        let structs_ = self.structs.as_slice();
+        let return_tokens = &self.return_;
+        let row_tokens = &self.row;
+        let param_exprs = self.param_exprs();
+
+        let (one_tokens, optional_tokens, many_tokens, stream_tokens) = if self.is_scalar() {
+            (
+                quote::quote! { row.try_get(0) },
+                quote::quote! { row.map(|row| row.try_get(0)).transpose() },
+                quote::quote! { rows.iter().map(|row| row.try_get(0)).collect() },
+                quote::quote! { |row| row.and_then(|row| row.try_get(0)) },
+            )
+        } else {
+            (
+                quote::quote! { #row_tokens::try_from(&row) },
+                quote::quote! { row.as_ref().map(#row_tokens::try_from).transpose() },
+                quote::quote! { rows.iter().map(#row_tokens::try_from).collect() },
+                quote::quote! { |row| row.and_then(|row| #row_tokens::try_from(&row)) },
+            )
+        };
+
+        let fn_body_tokens = match self.query.cmd.as_str() {
+            ":one" => quote::quote! {
+                let row = client.query_one(#sql, &[#(&#param_exprs),*]).await?;
+                #one_tokens
+            },
+            ":many" => quote::quote! {
+                let rows = client.query(#sql, &[#(&#param_exprs),*]).await?;
+                #many_tokens
+            },
+            ":exec" => quote::quote! {
+                client.execute(#sql, &[#(&#param_exprs),*]).await?;
+
+                Ok(())
+            },
+            ":execresult" | ":execrows" => quote::quote! {
+                client.execute(#sql, &[#(&#param_exprs),*]).await
+            },
+            _ => panic!("unknown query command: {}", self.query.cmd),
+        };
+
+        let optional_fn_tokens = if self.query.cmd == ":one" {
+            let optional_func_name = format_ident!("{}_optional", func_name);
+            quote::quote! {
+                #doc
+                pub async fn #optional_func_name(
+                    client: &impl tokio_postgres::GenericClient,
+                    #params
+                ) -> Result<Option<#return_tokens>, tokio_postgres::Error> {
+                    let row = client.query_opt(#sql, &[#(&#param_exprs),*]).await?;
+                    #optional_tokens
+                }
+            }
+        } else if self.query.cmd == ":many" {
+            let stream_func_name = format_ident!("{}_stream", func_name);
+            let param_count = proc_macro2::Literal::usize_unsuffixed(param_exprs.len());
+            quote::quote! {
+                #doc
+                pub async fn #stream_func_name(
+                    client: &impl tokio_postgres::GenericClient,
+                    #params
+                ) -> Result<
+                    impl futures::Stream<Item = Result<#row_tokens, tokio_postgres::Error>>,
+                    tokio_postgres::Error,
+                > {
+                    let params: [&(dyn tokio_postgres::types::ToSql + Sync); #param_count] =
+                        [#(&#param_exprs),*];
+                    let rows = client.query_raw(#sql, params).await?;
+                    Ok(futures::StreamExt::map(rows, #stream_tokens))
+                }
+            }
+        } else {
+            TokenStream::new()
+        };
+
+        quote::quote! {
+            #(#structs_)*
+
+            #doc
+
+            pub async fn #func_name(
+                client: &impl tokio_postgres::GenericClient,
+                #params
+            ) -> Result<#return_tokens, tokio_postgres::Error> {
+                #fn_body_tokens
+            }
+
+            #optional_fn_tokens
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn sqlx_tokens(&self) -> TokenStream {
+        let func_name = format_ident!("{}", self.func_name.as_str());
+        let doc = self.doc_tokens();
+        let sql = self.sql_const();
+
+        let params = self.params.clone();
+        let structs_ = self.structs.as_slice();
+        let return_tokens = self.return_.clone();
+
+        let database = self.engine.sqlx_database();
 
         let exec_func_tokens = match self.query.cmd.as_str() {
             ":one" => quote::quote! { fetch_one },
             ":many" => quote::quote! { fetch_all },
-            ":exec" | ":execresult" | ":execrows" | ":copyfrom" => quote::quote! { execute },
+            ":exec" | ":execresult" | ":execrows" | ":execlastid" => quote::quote! { execute },
             _ => panic!("unknown query command: {}", self.query.cmd),
         };
 
-        let query_func_tokens = if query_cols.len() == 1 {
-            quote::quote! { query_scalar }
+        let query_func_tokens = match self.query.cmd.as_str() {
+            ":one" | ":many" if self.is_scalar() => quote::quote! { query_scalar },
+            ":one" | ":many" => quote::quote! { query_as },
+            ":exec" | ":execresult" | ":execrows" | ":execlastid" => quote::quote! { query },
+            _ => panic!("unknown query command: {}", self.query.cmd),
+        };
+
+        // Slices change the number of placeholders, so their SQL is built and bound at call time.
+        let (prelude_tokens, query_tokens) = if self.has_slices() {
+            let row_tokens = &self.row;
+            let query_func_tokens = match self.query.cmd.as_str() {
+                ":one" | ":many" => quote::quote! { #query_func_tokens::<#database, #row_tokens> },
+                _ => quote::quote! { #query_func_tokens::<#database> },
+            };
+            let expansions = self
+                .param_fields()
+                .into_iter()
+                .zip(self.param_exprs())
+                .filter(|(field, _)| field.col.is_sqlc_slice)
+                .map(|(field, expr)| {
+                    let marker = format!("/*SLICE:{}*/?", field.col.name);
+                    quote::quote! { .replace(#marker, &slice_placeholders(#expr.len())) }
+                });
+            let binds =
+                self.param_fields()
+                    .into_iter()
+                    .zip(self.param_exprs())
+                    .map(|(field, expr)| {
+                        if field.col.is_sqlc_slice {
+                            quote::quote! {
+                                for value in #expr {
+                                    query = query.bind(value);
+                                }
+                            }
+                        } else {
+                            quote::quote! { query = query.bind(#expr); }
+                        }
+                    });
+            (
+                quote::quote! {
+                    let sql = #sql #(#expansions)*;
+                    let mut query = sqlx::#query_func_tokens(&sql);
+                    #(#binds)*
+                },
+                quote::quote! { query },
+            )
         } else {
-            match self.query.cmd.as_str() {
-                ":one" | ":many" => quote::quote! { query_as },
-                ":exec" | ":execresult" | ":execrows" | ":copyfrom" => quote::quote! { query },
-                _ => panic!("unknown query command: {}", self.query.cmd),
-            }
+            let params_bind_tokens = self
+                .param_exprs()
+                .into_iter()
+                .map(|expr| quote::quote! { .bind(#expr) });
+            (
+                TokenStream::new(),
+                quote::quote! { sqlx::#query_func_tokens(#sql) #(#params_bind_tokens)* },
+            )
         };
 
         let fn_body_tokens = match self.query.cmd.as_str() {
             ":one" | ":many" => quote::quote! {
-                let rec: #return_tokens = sqlx::#query_func_tokens(#sql)
-                #(#params_bind_tokens)*
+                #prelude_tokens
+                let rec: #return_tokens = #query_tokens
                 .#exec_func_tokens(db)
                 .await?;
 
                 Ok(rec)
             },
             ":exec" => quote::quote! {
-                sqlx::#query_func_tokens(#sql)
-                #(#params_bind_tokens)*
+                #prelude_tokens
+                #query_tokens
                 .#exec_func_tokens(db)
                 .await?;
 
                 Ok(())
             },
             ":execrows" => quote::quote! {
-                let rec = sqlx::#query_func_tokens(#sql)
-                #(#params_bind_tokens)*
+                #prelude_tokens
+                let rec = #query_tokens
                 .#exec_func_tokens(db)
                 .await?;
 
                 Ok(rec.rows_affected())
             },
-            ":execresult" | ":copyfrom" => quote::quote! {
-                sqlx::#query_func_tokens(#sql)
-                #(#params_bind_tokens)*
-                .#exec_func_tokens(db)
-                .await?;
+            ":execlastid" => {
+                let (_, last_insert_id) = self
+                    .engine
+                    .sqlx_last_insert_id()
+                    .expect(":execlastid should be supported by the engine");
+                quote::quote! {
+                    #prelude_tokens
+                    let rec = #query_tokens
+                    .#exec_func_tokens(db)
+                    .await?;
 
-                Err(sqlx::Error::TypeNotFound {
-                    type_name: String::from(#cmd),
-                })
+                    Ok(rec.#last_insert_id())
+                }
+            }
+            ":execresult" => quote::quote! {
+                #prelude_tokens
+                #query_tokens
+                .#exec_func_tokens(db)
+                .await
             },
             _ => panic!("unknown query command: {}", self.query.cmd),
         };
 
+        let optional_tokens = if self.query.cmd == ":one" {
+            let optional_func_name = format_ident!("{}_optional", func_name);
+            quote::quote! {
+                #doc
+                pub async fn #optional_func_name<'e, E>(
+                    db: E,
+                    #params
+                ) -> Result<Option<#return_tokens>, sqlx::Error>
+                where
+                    E: sqlx::Executor<'e, Database = #database>,
+                {
+                    #prelude_tokens
+                    let rec: Option<#return_tokens> = #query_tokens
+                    .fetch_optional(db)
+                    .await?;
+
+                    Ok(rec)
+                }
+            }
+        } else if self.query.cmd == ":many" && !self.has_slices() {
+            let row_tokens = &self.row;
+            let stream_func_name = format_ident!("{}_stream", func_name);
+            quote::quote! {
+                #doc
+                pub fn #stream_func_name<'e, E>(
+                    db: E,
+                    #params
+                ) -> impl futures::Stream<Item = Result<#row_tokens, sqlx::Error>> + 'e
+                where
+                    E: sqlx::Executor<'e, Database = #database> + 'e,
+                {
+                    #query_tokens
+                    .fetch(db)
+                }
+            }
+        } else {
+            TokenStream::new()
+        };
+
         quote::quote! {
             #(#structs_)*
 
+            #doc
+
             pub async fn #func_name<'e, E>(db: E, #params) -> Result<#return_tokens, sqlx::Error>
             where
-                E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+                E: sqlx::Executor<'e, Database = #database>,
             {
                 #fn_body_tokens
             }
+
+            #optional_tokens
+        }
+    }
+}
+
+impl quote::ToTokens for GenQuery<'_> {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        eprintln!("query::ToTokens: {:?}", self.query.name);
+        self.sql_const_tokens().to_tokens(tokens);
+        match (self.driver, self.query.cmd.as_str()) {
+            (Driver::Sqlx, ":copyfrom") => self.sqlx_copyfrom_tokens(),
+            (Driver::Sqlx, ":batchexec" | ":batchmany" | ":batchone") => self.sqlx_batch_tokens(),
+            (Driver::Sqlx, _) => self.sqlx_tokens(),
+            (Driver::TokioPostgres, ":copyfrom") => self.tokio_postgres_copyfrom_tokens(),
+            (Driver::TokioPostgres, ":batchexec" | ":batchmany" | ":batchone") => {
+                self.tokio_postgres_batch_tokens()
+            }
+            (Driver::TokioPostgres, _) => self.tokio_postgres_tokens(),
         }
         .to_tokens(tokens);
     }
@@ -199,17 +1457,47 @@ impl<'query> quote::ToTokens for GenQuery<'query> {
 
 pub struct Generator {
     pub req: plugin::GenerateRequest,
+    pub driver: Driver,
+    pub engine: Engine,
+    pub overrides: Vec<Override>,
+    pub rename: Rename,
+    pub composite_types: Vec<CompositeType>,
+    pub types: TypeOptions,
+    pub output: Output,
     pub structs: elsa::vec::FrozenVec<Box<GenStruct>>,
 }
 
 impl Generator {
-    pub fn generate(&mut self) -> Vec<plugin::File> {
-        let queries = self.gen_queries_file();
-
-        vec![plugin::File {
-            name: "queries.rs".to_string(),
-            contents: queries.as_bytes().to_vec(),
-        }]
+    pub fn generate(&mut self) -> Result<Vec<plugin::File>, String> {
+        if self.driver == Driver::TokioPostgres && self.engine != Engine::Postgresql {
+            return Err(format!(
+                "driver tokio-postgres does not support the {} engine",
+                self.engine
+            ));
+        }
+        for override_ in &self.overrides {
+            override_.validate()?;
+        }
+        self.rename.validate()?;
+        for config in &self.composite_types {
+            let known = self.catalog_schemas().any(|schema| {
+                schema.composite_types.iter().any(|composite| {
+                    config.matches(&schema.name, &composite.name, self.default_schema())
+                })
+            });
+            if !known {
+                return Err(format!(
+                    "composite_types {:?} is not a composite type of the catalog",
+                    config.name
+                ));
+            }
+        }
+        self.types.validate(self.driver)?;
+        if self.output.split_files {
+            self.gen_split_files()
+        } else {
+            self.gen_single_file()
+        }
     }
 
     fn struct_find(&self, name: &str, cols: &[plugin::Column]) -> Option<&GenStruct> {
@@ -226,159 +1514,1167 @@ impl Generator {
         self.struct_find(name, cols).is_some()
     }
 
-    fn find_or_create_struct(&self, name: &str, cols: &[plugin::Column]) -> (&GenStruct, bool) {
+    fn find_or_create_struct(
+        &self,
+        name: &str,
+        comment: &str,
+        cols: &[plugin::Column],
+    ) -> Result<(&GenStruct, bool), String> {
         if self.struct_exists(name, cols) {
-            return (
+            return Ok((
                 self.struct_find(name, cols)
                     .expect("existing struct should be found"),
                 false,
-            );
+            ));
         }
         let mut struct_ = GenStruct {
             name: String::from(name),
+            comment: String::from(comment),
             fields: Vec::new(),
             cols: cols.to_vec(),
+            driver: self.driver,
+            engine: self.engine,
         };
         for col in cols {
-            let field = GenField { col: col.clone() };
-            struct_.fields.push(field);
+            struct_.fields.push(self.gen_field(col)?);
         }
         self.structs.push(Box::new(struct_));
-        (self.structs.last().expect("last struct should exist"), true)
+        Ok((self.structs.last().expect("last struct should exist"), true))
     }
 
-    #[allow(clippy::too_many_lines)]
-    fn gen_queries_file(&self) -> String {
-        let queries = self.req.queries.iter().map(|query| {
-            let mut new_structs = Vec::new();
-            let params_cols: Vec<plugin::Column> = query
-                .params
-                .iter()
-                .filter_map(|param| param.column.clone())
-                .collect();
-            let params = match params_cols.len() {
-                3.. => {
-                    let (info_struct, _) = self.find_or_create_struct(
-                        format!("{}Info", query.name).as_str(),
-                        params_cols.as_slice(),
-                    );
-                    let struct_ = Params::Struct {
-                        name: ident::to_snake(info_struct.name.as_str()),
-                        type_: info_struct.name.clone(),
-                    };
-                    new_structs.push(info_struct);
-                    struct_
-                }
-                1..3 => Params::DBType(
-                    query
-                        .params
+    fn gen_field(&self, col: &plugin::Column) -> Result<GenField, String> {
+        Ok(GenField {
+            name: self.rename.column(&col.name, ident::to_snake(&col.name)),
+            col: col.clone(),
+            type_: self.column_type(col)?,
+            embed: self
+                .embed_table(col)
+                .map(|(_, table)| {
+                    table
+                        .columns
                         .iter()
-                        .filter_map(|param| param.column.clone())
-                        .collect(),
-                ),
-                0 => Params::None,
-            };
-            let query_cols: Vec<plugin::Column> = query
-                .columns
-                .iter()
-                .filter_map(|c| match c.r#type {
-                    Some(ref t) if t.name == "void" => None,
-                    _ => Some(c.clone()),
+                        .map(|col| self.gen_field(col))
+                        .collect()
                 })
-                .collect();
-            let return_name = if query_cols.is_empty() {
-                if query.cmd == ":execresult" {
-                    panic!(":execresult not supported");
-                } else if query.cmd == ":execrows" {
-                    quote::quote! { u64 }
-                } else {
-                    quote::quote! { () }
+                .transpose()?,
+        })
+    }
+
+    /// User defined schemas, skipping the ones built into the engine.
+    fn catalog_schemas(&self) -> impl Iterator<Item = &plugin::Schema> {
+        self.req
+            .catalog
+            .iter()
+            .flat_map(|catalog| catalog.schemas.iter())
+            .filter(|schema| !matches!(schema.name.as_str(), "pg_catalog" | "information_schema"))
+    }
+
+    fn is_default_schema(&self, schema: &str) -> bool {
+        schema.is_empty()
+            || self
+                .req
+                .catalog
+                .as_ref()
+                .is_some_and(|catalog| catalog.default_schema == schema)
+    }
+
+    /// Rust type name of a catalog type, prefixed by its schema outside of the default one.
+    fn user_type_name(&self, schema: &plugin::Schema, name: &str) -> String {
+        if self.is_default_schema(&schema.name) {
+            self.rename.type_(name, ident::to_upper_camel(name))
+        } else {
+            let qualified = format!("{}.{name}", schema.name);
+            self.rename.type_(
+                &qualified,
+                ident::to_upper_camel(format!("{}_{name}", schema.name)),
+            )
+        }
+    }
+
+    /// SQL name a derived type is looked up by. tokio-postgres only compares the bare name.
+    fn sql_type_name(&self, schema: &plugin::Schema, name: &str) -> String {
+        if self.driver == Driver::TokioPostgres || self.is_default_schema(&schema.name) {
+            name.to_string()
+        } else {
+            format!("{}.{name}", schema.name)
+        }
+    }
+
+    fn schema_matches(&self, type_: &plugin::Identifier, schema: &plugin::Schema) -> bool {
+        if type_.schema.is_empty() {
+            self.is_default_schema(&schema.name)
+        } else {
+            type_.schema == schema.name
+        }
+    }
+
+    fn gen_enums(&self) -> Result<Vec<GenEnum>, String> {
+        self.catalog_schemas()
+            .flat_map(|schema| schema.enums.iter().map(move |enum_| (schema, enum_)))
+            .map(|(schema, enum_)| {
+                let prefix = ident::to_upper_camel(&enum_.name);
+                let mut variants: Vec<(String, String)> = Vec::new();
+                for val in &enum_.vals {
+                    let variant = self.rename.value(
+                        &enum_.name,
+                        val,
+                        ident::strip_enum_prefix(&prefix, &ident::to_upper_camel(val)),
+                    );
+                    if syn::parse_str::<syn::Ident>(&variant).is_err() {
+                        return Err(format!(
+                            "enum {}: value {val:?} does not make an identifier, name it in \
+                             rename.values",
+                            enum_.name
+                        ));
+                    }
+                    if let Some((_, other)) = variants.iter().find(|(name, _)| *name == variant) {
+                        return Err(format!(
+                            "enum {}: values {other:?} and {val:?} are both named {variant}, \
+                             rename one in rename.values",
+                            enum_.name
+                        ));
+                    }
+                    variants.push((variant, val.clone()));
                 }
-            } else if query_cols.len() == 1 {
-                let col = query_cols.first().expect("first col should exist");
-                convert_postgres_type(col)
+                Ok(GenEnum {
+                    name: self.user_type_name(schema, &enum_.name),
+                    comment: enum_.comment.clone(),
+                    type_name: self.sql_type_name(schema, &enum_.name),
+                    variants,
+                    driver: self.driver,
+                    engine: self.engine,
+                })
+            })
+            .collect()
+    }
+
+    fn default_schema(&self) -> &str {
+        self.req
+            .catalog
+            .as_ref()
+            .map_or("", |catalog| catalog.default_schema.as_str())
+    }
+
+    /// Attributes of a composite type, taken from the `composite_types` option.
+    fn composite_columns(
+        &self,
+        schema: &plugin::Schema,
+        composite: &plugin::CompositeType,
+    ) -> Option<Vec<plugin::Column>> {
+        self.composite_types
+            .iter()
+            .find(|config| config.matches(&schema.name, &composite.name, self.default_schema()))
+            .map(CompositeType::columns)
+    }
+
+    fn gen_composites(&self) -> Result<Vec<GenComposite>, String> {
+        if self.engine != Engine::Postgresql {
+            return Ok(Vec::new());
+        }
+        self.catalog_schemas()
+            .flat_map(|schema| {
+                schema
+                    .composite_types
+                    .iter()
+                    .map(move |composite| (schema, composite))
+            })
+            .filter_map(|(schema, composite)| {
+                let cols = self.composite_columns(schema, composite)?;
+                let fields = match cols.iter().map(|col| self.gen_field(col)).collect() {
+                    Ok(fields) => fields,
+                    Err(e) => return Some(Err(format!("{}: {e}", composite.name))),
+                };
+                Some(Ok(GenComposite {
+                    name: self.user_type_name(schema, &composite.name),
+                    comment: composite.comment.clone(),
+                    type_name: self.sql_type_name(schema, &composite.name),
+                    fields,
+                    driver: self.driver,
+                }))
+            })
+            .collect()
+    }
+
+    /// The generated enum or composite struct a column refers to, if its type is defined in the
+    /// catalog. Composite types missing from the `composite_types` option are an error.
+    fn column_user_type(&self, col: &plugin::Column) -> Result<Option<TokenStream>, String> {
+        let Some(type_) = col.r#type.as_ref() else {
+            return Ok(None);
+        };
+        let is_enum = |schema: &plugin::Schema| schema.enums.iter().any(|e| e.name == type_.name);
+        let is_composite = |schema: &plugin::Schema| {
+            self.engine == Engine::Postgresql
+                && schema.composite_types.iter().any(|c| c.name == type_.name)
+        };
+        let Some(schema) = self.catalog_schemas().find(|schema| {
+            self.schema_matches(type_, schema) && (is_enum(schema) || is_composite(schema))
+        }) else {
+            return Ok(None);
+        };
+        if !is_enum(schema)
+            && !self
+                .composite_types
+                .iter()
+                .any(|config| config.matches(&schema.name, &type_.name, self.default_schema()))
+        {
+            let qualified = if self.is_default_schema(&schema.name) {
+                type_.name.clone()
             } else {
-                let (ret_struct, new) = self.find_or_create_struct(
-                    ident::to_upper_camel(format!("{}Row", query.name)).as_str(),
-                    query_cols.as_slice(),
-                );
-                if new {
-                    new_structs.push(ret_struct);
-                }
-                let ret_ident = format_ident!("{}", ret_struct.name.as_str());
-                if query.cmd == ":many" {
-                    let vec_ident = format_ident!("{}", "Vec");
-                    quote::quote! { #vec_ident<#ret_ident> }
-                } else {
-                    ret_ident.to_token_stream()
-                }
+                format!("{}.{}", schema.name, type_.name)
             };
+            return Err(format!(
+                "column {}: composite type {qualified:?} has no known fields, list them in \
+                 composite_types or add an override",
+                col.name
+            ));
+        }
+        let name = self.user_type_name(schema, &type_.name);
+        Ok(Some(format_ident!("{}", name).to_token_stream()))
+    }
 
-            GenQuery {
-                query,
-                structs: new_structs,
-                params,
-                return_: return_name,
-            }
-        });
-        let file = quote::quote! {
-            /// This file is @generated by sqlc-gen-rust.
-            #(#queries)*
-        };
-        pretty_print_ts(&file)
+    /// The configured override for a column, `column` overrides taking precedence.
+    fn column_override(&self, col: &plugin::Column) -> Option<&Override> {
+        self.overrides
+            .iter()
+            .find(|override_| override_.matches_column(col, self.default_schema()))
+            .or_else(|| {
+                let type_ = col.r#type.as_ref()?;
+                self.overrides.iter().find(|override_| {
+                    override_.matches_db_type(type_, col.not_null, array_dims(col))
+                })
+            })
     }
-}
 
-#[allow(clippy::match_same_arms)]
-fn convert_postgres_type(col: &plugin::Column) -> proc_macro2::TokenStream {
-    let type_ = col
-        .r#type
-        .as_ref()
-        .expect("col type expected")
-        .name
-        .as_str();
-    let not_null = col.not_null;
-    let is_array = col.is_array;
-    let ident = match type_ {
-        "serial" | "serial4" | "pg_catalog.serial4" => quote::quote! { i32 },
-        "bigserial" | "serial8" | "pg_catalog.serial8" => quote::quote! { i64 },
-        "smallserial" | "serial2" | "pg_catalog.serial2" => quote::quote! { i16 },
-        "integer" | "int" | "int4" | "pg_catalog.int4" => quote::quote! { i32 },
-        "bigint" | "int8" | "pg_catalog.int8" => quote::quote! { i64 },
-        "smallint" | "int2" | "pg_catalog.int2" => quote::quote! { i16 },
-        "float" | "double precision" | "float8" | "pg_catalog.float8" => quote::quote! { f64 },
-        "real" | "float4" | "pg_catalog.float4" => quote::quote! { f32 },
-        "numeric" | "pg_catalog.numeric" | "money" => quote::quote! { String },
-        "boolean" | "bool" | "pg_catalog.bool" => quote::quote! { bool },
-        "json" | "jsonb" => quote::quote! { sqlx::types::Json<serde_json::Value> },
-        "bytea" | "blob" | "pg_catalog.bytea" => quote::quote! { Vec<u8> },
-        "date" | "pg_catalog.date" => quote::quote! { chrono::NaiveDate },
-        "time" | "pg_catalog.time" => quote::quote! { chrono::NaiveTime },
-        "timestamp" | "pg_catalog.timestamp" => quote::quote! { chrono::NaiveDateTime },
-        "timestamptz" | "pg_catalog.timestamptz" => quote::quote! { chrono::DateTime<chrono::Utc> },
-        "text" | "pg_catalog.varchar" | "pg_catalog.bpchar" | "string" | "citext" | "name" => {
-            quote::quote! { String }
-        }
-        "uuid" => quote::quote! { uuid::Uuid },
-        "inet" | "cidr" => quote::quote! { std::net::IpAddr },
-        "macaddr" | "macaddr8" => quote::quote! { eui48::MacAddress },
-        "ltree" | "lquery" | "ltxtquery" => quote::quote! { String },
-        "interval" | "pg_catalog.interval" => quote::quote! { chrono::Duration },
-        _ => quote::quote! { sqlx::types::Json<serde_json::Value> },
-    };
+    /// The catalog table a `sqlc.embed()` column stands for.
+    fn embed_table(&self, col: &plugin::Column) -> Option<(&plugin::Schema, &plugin::Table)> {
+        let embed = col.embed_table.as_ref().filter(|_| is_embed(col))?;
+        self.catalog_tables().find(|(schema, table)| {
+            self.schema_matches(embed, schema)
+                && table.rel.as_ref().is_some_and(|rel| rel.name == embed.name)
+        })
+    }
 
-    if is_array {
-        if not_null {
-            quote::quote! { Vec<#ident> }
+    /// Attaches an INSERT parameter sqlc reports without a table, e.g. `@args::jsonb`, to the
+    /// inserted table when it has a column of that name, so `column` overrides apply.
+    fn insert_param_column(&self, query: &plugin::Query, col: plugin::Column) -> plugin::Column {
+        let Some(insert) = query
+            .insert_into_table
+            .as_ref()
+            .filter(|_| col.table.is_none())
+        else {
+            return col;
+        };
+        let has_column = self.catalog_tables().any(|(schema, table)| {
+            self.schema_matches(insert, schema)
+                && table
+                    .rel
+                    .as_ref()
+                    .is_some_and(|rel| rel.name == insert.name)
+                && table
+                    .columns
+                    .iter()
+                    .any(|table_col| table_col.name == col.name)
+        });
+        if has_column {
+            plugin::Column {
+                table: Some(insert.clone()),
+                ..col
+            }
         } else {
-            quote::quote! { Option<Vec<#ident>> }
+            col
         }
-    } else if not_null {
-        quote::quote! { #ident }
-    } else {
-        quote::quote! { Option<#ident> }
     }
-}
+
+    fn column_type(&self, col: &plugin::Column) -> Result<TokenStream, String> {
+        if let Some((schema, table)) = self.embed_table(col) {
+            let rel = table
+                .rel
+                .as_ref()
+                .expect("embedded table should have a name");
+            return Ok(format_ident!("{}", self.model_name(schema, &rel.name)).to_token_stream());
+        }
+        let dims = array_dims(col);
+        if let Some(override_) = self.column_override(col) {
+            let rust_type = override_.rust_type();
+            // sqlx has no multi-dimensional array type, so a column override stands for the whole
+            // array, while a scalar db_type override still needs one.
+            let multi_dims_sqlx = dims > 1 && self.driver == Driver::Sqlx;
+            if override_.is_array_db_type() || (override_.column.is_some() && multi_dims_sqlx) {
+                return Ok(if col.not_null {
+                    rust_type
+                } else {
+                    quote::quote! { Option<#rust_type> }
+                });
+            }
+            if !multi_dims_sqlx {
+                return Ok(wrap_column_type(col, &rust_type));
+            }
+        }
+        if dims > 1 && self.driver == Driver::Sqlx {
+            return Err(format!(
+                "column {}: {dims}-dimensional arrays are not supported by sqlx, which only decodes \
+                 one-dimensional arrays",
+                col.name
+            ));
+        }
+        if let Some(user_type) = self.column_user_type(col)? {
+            return Ok(wrap_column_type(col, &user_type));
+        }
+        match self.engine {
+            Engine::Postgresql => convert_postgres_type(col, self.driver, &self.types),
+            Engine::Mysql => convert_mysql_type(col, &self.types),
+            Engine::Sqlite => convert_sqlite_type(col, &self.types),
+        }
+        .map_err(|e| {
+            if col
+                .r#type
+                .as_ref()
+                .is_some_and(|type_| type_.name.is_empty())
+            {
+                format!("column {}: {e}", col.name)
+            } else {
+                format!("column {}: {e}, add an override", col.name)
+            }
+        })
+    }
+
+    /// Rust name of the model of a table: the singular table name, prefixed by its schema outside
+    /// of the default one.
+    fn model_name(&self, schema: &plugin::Schema, table: &str) -> String {
+        let singular = ident::singularize(table);
+        if self.is_default_schema(&schema.name) {
+            self.rename.type_(table, ident::to_upper_camel(singular))
+        } else {
+            self.rename.type_(
+                &format!("{}.{table}", schema.name),
+                ident::to_upper_camel(format!("{}_{singular}", schema.name)),
+            )
+        }
+    }
+
+    fn catalog_tables(&self) -> impl Iterator<Item = (&plugin::Schema, &plugin::Table)> {
+        self.catalog_schemas()
+            .flat_map(|schema| schema.tables.iter().map(move |table| (schema, table)))
+    }
+
+    /// One model struct per catalog table.
+    fn gen_models(&self) -> Result<Vec<&GenStruct>, String> {
+        self.catalog_tables()
+            .filter_map(|(schema, table)| {
+                let rel = table.rel.as_ref()?;
+                Some(
+                    self.find_or_create_struct(
+                        &self.model_name(schema, &rel.name),
+                        &table.comment,
+                        &table.columns,
+                    )
+                    .map(|(model, _)| model)
+                    .map_err(|e| format!("{}: {e}", rel.name)),
+                )
+            })
+            .collect()
+    }
+
+    /// The model of the table whose columns are exactly the query columns, in order, or the
+    /// model of the only column when it is a `sqlc.embed()` table.
+    fn table_model(&self, cols: &[plugin::Column]) -> Option<&GenStruct> {
+        if let [col] = cols {
+            if let Some((schema, table)) = self.embed_table(col) {
+                let rel = table.rel.as_ref()?;
+                return self.struct_find(&self.model_name(schema, &rel.name), &table.columns);
+            }
+        }
+        let (schema, table) = self.catalog_tables().find(|(_, table)| {
+            let Some(rel) = table.rel.as_ref() else {
+                return false;
+            };
+            table.columns.len() == cols.len()
+                && table.columns.iter().zip(cols).all(|(table_col, col)| {
+                    col.table.as_ref().is_some_and(|col_table| {
+                        col_table.name == rel.name && col_table.schema == rel.schema
+                    }) && table_col.name == col.name
+                        && table_col.r#type == col.r#type
+                        && table_col.not_null == col.not_null
+                        && table_col.is_array == col.is_array
+                        && table_col.array_dims == col.array_dims
+                        && table_col.unsigned == col.unsigned
+                })
+        })?;
+        let rel = table.rel.as_ref()?;
+        self.struct_find(&self.model_name(schema, &rel.name), &table.columns)
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn gen_query<'query>(
+        &'query self,
+        query: &'query plugin::Query,
+    ) -> Result<GenQuery<'query>, String> {
+        match query.cmd.as_str() {
+            ":copyfrom" if self.engine != Engine::Postgresql => {
+                return Err(format!(
+                    "{}: :copyfrom is only supported by the postgresql engine",
+                    query.name
+                ));
+            }
+            ":execlastid" if self.engine.sqlx_last_insert_id().is_none() => {
+                return Err(format!(
+                    "{}: :execlastid is not supported by the {} engine, use RETURNING with :one instead",
+                    query.name, self.engine
+                ));
+            }
+            ":copyfrom" | ":batchexec" | ":batchmany" | ":batchone"
+                if query
+                    .params
+                    .iter()
+                    .any(|param| param.column.as_ref().is_some_and(|col| col.is_sqlc_slice)) =>
+            {
+                return Err(format!(
+                    "{}: sqlc.slice() is not supported by {}",
+                    query.name, query.cmd
+                ));
+            }
+            ":one" | ":many" | ":exec" | ":execresult" | ":execrows" | ":execlastid"
+            | ":copyfrom" | ":batchexec" | ":batchmany" | ":batchone" => {}
+            cmd => return Err(format!("{}: unsupported query command {cmd}", query.name)),
+        }
+        let mut new_structs = Vec::new();
+        let params_cols: Vec<plugin::Column> = query
+            .params
+            .iter()
+            .filter_map(|param| param.column.clone())
+            .map(|col| self.insert_param_column(query, col))
+            .collect();
+        if query.cmd == ":copyfrom" && self.driver == Driver::Sqlx {
+            for col in &params_cols {
+                self.check_copy_in_column(col)
+                    .map_err(|e| format!("{}: {e}", query.name))?;
+            }
+        }
+        let params = match params_cols.len() {
+            0 => Params::None,
+            1..3 if !matches!(
+                query.cmd.as_str(),
+                ":copyfrom" | ":batchexec" | ":batchmany" | ":batchone"
+            ) =>
+            {
+                Params::DBType(
+                    params_cols
+                        .iter()
+                        .map(|col| self.gen_field(col))
+                        .collect::<Result<_, _>>()
+                        .map_err(|e| format!("{}: {e}", query.name))?,
+                )
+            }
+            _ => {
+                let info_name = format!("{}Info", query.name);
+                let (info_struct, _) = self
+                    .find_or_create_struct(
+                        self.rename.type_(&info_name, info_name.clone()).as_str(),
+                        "",
+                        params_cols.as_slice(),
+                    )
+                    .map_err(|e| format!("{}: {e}", query.name))?;
+                let struct_ = Params::Struct {
+                    name: ident::to_snake(info_struct.name.as_str()),
+                    type_: info_struct.name.clone(),
+                };
+                new_structs.push(info_struct);
+                struct_
+            }
+        };
+        let query_cols: Vec<plugin::Column> = query
+            .columns
+            .iter()
+            .filter_map(|c| match c.r#type {
+                Some(ref t) if t.name == "void" => None,
+                _ => Some(c.clone()),
+            })
+            .collect();
+        let mut row_name = quote::quote! { () };
+        let return_name = if query.cmd == ":execresult" {
+            match self.driver {
+                Driver::Sqlx => self.engine.sqlx_query_result(),
+                Driver::TokioPostgres => quote::quote! { u64 },
+            }
+        } else if query.cmd == ":execrows" || query.cmd == ":copyfrom" {
+            quote::quote! { u64 }
+        } else if query.cmd == ":execlastid" {
+            let (id_type, _) = self
+                .engine
+                .sqlx_last_insert_id()
+                .expect(":execlastid should be supported by the engine");
+            id_type
+        } else if query_cols.is_empty() || query.cmd == ":exec" || query.cmd == ":batchexec" {
+            quote::quote! { () }
+        } else if query_cols.len() == 1 && !is_embed(&query_cols[0]) {
+            let col = query_cols.first().expect("first col should exist");
+            row_name = self
+                .column_type(col)
+                .map_err(|e| format!("{}: {e}", query.name))?;
+            if query.cmd == ":many" || query.cmd == ":batchmany" {
+                quote::quote! { Vec<#row_name> }
+            } else {
+                row_name.clone()
+            }
+        } else {
+            let ret_struct = if let Some(model) = self.table_model(&query_cols) {
+                model
+            } else {
+                let (ret_struct, new) = self
+                    .find_or_create_struct(
+                        self.rename
+                            .type_(
+                                &format!("{}Row", query.name),
+                                ident::to_upper_camel(format!("{}Row", query.name)),
+                            )
+                            .as_str(),
+                        "",
+                        query_cols.as_slice(),
+                    )
+                    .map_err(|e| format!("{}: {e}", query.name))?;
+                if new {
+                    new_structs.push(ret_struct);
+                }
+                ret_struct
+            };
+            let ret_ident = format_ident!("{}", ret_struct.name.as_str());
+            row_name = ret_ident.to_token_stream();
+            if query.cmd == ":many" || query.cmd == ":batchmany" {
+                let vec_ident = format_ident!("{}", "Vec");
+                quote::quote! { #vec_ident<#ret_ident> }
+            } else {
+                ret_ident.to_token_stream()
+            }
+        };
+        let return_name = if query.cmd.starts_with(":batch") {
+            let error_type = self.driver.error_type();
+            quote::quote! { Vec<Result<#return_name, #error_type>> }
+        } else {
+            return_name
+        };
+
+        Ok(GenQuery {
+            query,
+            func_name: self.rename.query(&query.name, ident::to_snake(&query.name)),
+            structs: new_structs,
+            params,
+            return_: return_name,
+            row: row_name,
+            driver: self.driver,
+            engine: self.engine,
+        })
+    }
+
+    /// Rejects the columns sqlx cannot encode for a binary `COPY`: `copy_in_field` writes the
+    /// encoded value as is, without the fixes sqlx applies once the parameter types are known.
+    fn check_copy_in_column(&self, col: &plugin::Column) -> Result<(), String> {
+        let type_name = col.r#type.as_ref().map_or("", |type_| type_.name.as_str());
+        if type_name.strip_prefix("pg_catalog.").unwrap_or(type_name) == "json" {
+            return Err(format!(
+                "column {}: json cannot be copied with sqlx, use jsonb",
+                col.name
+            ));
+        }
+        if self.column_user_type(col)?.is_some() {
+            if col.is_array {
+                return Err(format!(
+                    "column {}: arrays of enums and composite types cannot be copied with sqlx",
+                    col.name
+                ));
+            }
+            // Records carry the type of every field, which sqlx cannot resolve for custom
+            // types without a round trip to the database.
+            let fields = self.copy_in_composite_fields(col).unwrap_or_default();
+            for field in &fields {
+                let field_type = field
+                    .r#type
+                    .as_ref()
+                    .map_or("", |type_| type_.name.as_str());
+                if self.column_user_type(field)?.is_some() || !sqlx_copy_native_type(field_type) {
+                    return Err(format!(
+                        "column {}: composite field {} of type {field_type} cannot be copied with \
+                         sqlx",
+                        col.name, field.name
+                    ));
+                }
+            }
+            return Ok(());
+        }
+        if col.is_array
+            && !sqlx_copy_native_type(type_name)
+            && sqlx_copy_element_oid(type_name).is_none()
+        {
+            return Err(format!(
+                "column {}: {}[] cannot be copied with sqlx",
+                col.name,
+                type_name.strip_prefix("pg_catalog.").unwrap_or(type_name)
+            ));
+        }
+        Ok(())
+    }
+
+    /// The configured fields of the composite type of a column.
+    fn copy_in_composite_fields(&self, col: &plugin::Column) -> Option<Vec<plugin::Column>> {
+        let type_ = col.r#type.as_ref()?;
+        self.catalog_schemas()
+            .filter(|schema| self.schema_matches(type_, schema))
+            .find_map(|schema| {
+                let composite = schema
+                    .composite_types
+                    .iter()
+                    .find(|composite| composite.name == type_.name)?;
+                self.composite_columns(schema, composite)
+            })
+    }
+
+    /// Private helpers used by the generated functions of some queries.
+    fn gen_helpers(&self, queries: &[GenQuery]) -> TokenStream {
+        let copy_in_tokens = if self.driver == Driver::Sqlx
+            && queries.iter().any(|query| query.query.cmd == ":copyfrom")
+        {
+            copy_in_field_tokens()
+        } else {
+            TokenStream::new()
+        };
+        let copy_in_array_tokens =
+            if self.driver == Driver::Sqlx && queries.iter().any(GenQuery::copies_patched_arrays) {
+                copy_in_array_field_tokens()
+            } else {
+                TokenStream::new()
+            };
+        let slice_tokens = if queries.iter().any(GenQuery::has_slices) {
+            slice_placeholders_tokens()
+        } else {
+            TokenStream::new()
+        };
+        quote::quote! {
+            #copy_in_tokens
+            #copy_in_array_tokens
+            #slice_tokens
+        }
+    }
+
+    /// Enums, composite types and table models shared by every query.
+    fn gen_models_tokens(&self) -> Result<TokenStream, String> {
+        let enums = self.gen_enums()?;
+        let composites = self.gen_composites()?;
+        let models = self.gen_models()?;
+        let interval =
+            if self.driver == Driver::TokioPostgres && self.uses_postgres_type("interval") {
+                tokio_postgres_interval_tokens()
+            } else {
+                TokenStream::new()
+            };
+        Ok(quote::quote! {
+            #interval
+            #(#enums)*
+            #(#composites)*
+            #(#models)*
+        })
+    }
+
+    /// Whether a table, query column or parameter has the given built-in PostgreSQL type.
+    fn uses_postgres_type(&self, name: &str) -> bool {
+        let table_cols = self
+            .catalog_tables()
+            .flat_map(|(_, table)| table.columns.iter());
+        let query_cols = self.req.queries.iter().flat_map(|query| {
+            query.columns.iter().chain(
+                query
+                    .params
+                    .iter()
+                    .filter_map(|param| param.column.as_ref()),
+            )
+        });
+        table_cols.chain(query_cols).any(|col| {
+            col.r#type.as_ref().is_some_and(|type_| {
+                type_
+                    .name
+                    .strip_prefix("pg_catalog.")
+                    .unwrap_or(&type_.name)
+                    == name
+            })
+        })
+    }
+
+    fn gen_single_file(&self) -> Result<Vec<plugin::File>, String> {
+        let models = self.gen_models_tokens()?;
+        let queries = self
+            .req
+            .queries
+            .iter()
+            .map(|query| self.gen_query(query))
+            .collect::<Result<Vec<_>, String>>()?;
+        let helpers = self.gen_helpers(&queries);
+        let file = quote::quote! {
+            //! This file is @generated by sqlc-gen-rust.
+            #models
+            #(#queries)*
+            #helpers
+        };
+        Ok(vec![plugin::File {
+            name: self.output.queries_file_name.clone(),
+            contents: pretty_print_ts(&file).into_bytes(),
+        }])
+    }
+
+    /// One module per SQL file, a models module and a module file declaring and re-exporting them.
+    fn gen_split_files(&self) -> Result<Vec<plugin::File>, String> {
+        let models_module = module_name(&self.output.models_file_name)?;
+        let models = self.gen_models_tokens()?;
+        let mut files = vec![plugin::File {
+            name: self.output.models_file_name.clone(),
+            contents: pretty_print_ts(&quote::quote! {
+                //! This file is @generated by sqlc-gen-rust.
+                #models
+            })
+            .into_bytes(),
+        }];
+        let mut modules = vec![models_module.clone()];
+
+        let mut filenames: Vec<&str> = Vec::new();
+        for query in &self.req.queries {
+            if !filenames.contains(&query.filename.as_str()) {
+                filenames.push(query.filename.as_str());
+            }
+        }
+        for filename in filenames {
+            let stem = std::path::Path::new(filename)
+                .file_stem()
+                .and_then(std::ffi::OsStr::to_str)
+                .unwrap_or(filename);
+            let file_name = format!(
+                "{}{}.rs",
+                ident::to_snake(stem).trim_start_matches("r#"),
+                self.output.files_suffix
+            );
+            let module = module_name(&file_name)?;
+            if modules.contains(&module) {
+                return Err(format!(
+                    "{filename}: module {module} is generated more than once"
+                ));
+            }
+            let queries = self
+                .req
+                .queries
+                .iter()
+                .filter(|query| query.filename == filename)
+                .map(|query| self.gen_query(query))
+                .collect::<Result<Vec<_>, String>>()?;
+            let helpers = self.gen_helpers(&queries);
+            let models_module = &models_module;
+            let file = quote::quote! {
+                //! This file is @generated by sqlc-gen-rust.
+                #[allow(unused_imports)]
+                use super::#models_module::*;
+
+                #(#queries)*
+                #helpers
+            };
+            files.push(plugin::File {
+                name: file_name,
+                contents: pretty_print_ts(&file).into_bytes(),
+            });
+            modules.push(module);
+        }
+
+        let file = quote::quote! {
+            //! This file is @generated by sqlc-gen-rust.
+            #(pub mod #modules;)*
+
+            #(pub use #modules::*;)*
+        };
+        files.push(plugin::File {
+            name: self.output.mod_file_name.clone(),
+            contents: pretty_print_ts(&file).into_bytes(),
+        });
+        Ok(files)
+    }
+}
+
+/// Maps a built-in PostgreSQL type to the Rust type the driver decodes it to, failing for types
+/// the driver cannot decode. sqlc reports most types as `pg_catalog.<name>`, and the aliases used
+/// in the schema otherwise.
+#[allow(clippy::match_same_arms)]
+fn convert_postgres_type(
+    col: &plugin::Column,
+    driver: Driver,
+    types: &TypeOptions,
+) -> Result<TokenStream, String> {
+    let type_ = col
+        .r#type
+        .as_ref()
+        .expect("col type expected")
+        .name
+        .as_str();
+    let sqlx = driver == Driver::Sqlx;
+    let range = |element: TokenStream| quote::quote! { sqlx::postgres::types::PgRange<#element> };
+    let ident = match type_.strip_prefix("pg_catalog.").unwrap_or(type_) {
+        "serial" | "serial4" | "integer" | "int" | "int4" => quote::quote! { i32 },
+        "bigserial" | "serial8" | "bigint" | "int8" => quote::quote! { i64 },
+        "smallserial" | "serial2" | "smallint" | "int2" => quote::quote! { i16 },
+        "float" | "double precision" | "float8" => quote::quote! { f64 },
+        "real" | "float4" => quote::quote! { f32 },
+        "numeric" | "decimal" => types.decimal(),
+        "money" if sqlx => quote::quote! { sqlx::postgres::types::PgMoney },
+        "boolean" | "bool" => quote::quote! { bool },
+        // The single byte `"char"`, `char(n)` is reported as `bpchar`.
+        "char" => quote::quote! { i8 },
+        "text" | "varchar" | "bpchar" | "string" | "citext" | "name" => quote::quote! { String },
+        "ltree" if sqlx => quote::quote! { sqlx::postgres::types::PgLTree },
+        "lquery" if sqlx && !col.is_array => quote::quote! { sqlx::postgres::types::PgLQuery },
+        "ltree" | "lquery" | "ltxtquery" if !sqlx => quote::quote! { String },
+        "bytea" | "blob" => quote::quote! { Vec<u8> },
+        "bit" | "varbit" | "bit varying" => quote::quote! { bit_vec::BitVec },
+        "json" | "jsonb" if sqlx => quote::quote! { sqlx::types::Json<serde_json::Value> },
+        "json" | "jsonb" => quote::quote! { serde_json::Value },
+        "uuid" => quote::quote! { uuid::Uuid },
+        "date" => types.date(),
+        "time" => types.time(),
+        "timetz" if sqlx => types.timetz(),
+        "timestamp" => types.timestamp(),
+        "timestamptz" => types.timestamptz(),
+        "interval" if sqlx => quote::quote! { sqlx::postgres::types::PgInterval },
+        "interval" => quote::quote! { PgInterval },
+        "inet" | "cidr" if sqlx => quote::quote! { sqlx::types::ipnetwork::IpNetwork },
+        "inet" => quote::quote! { std::net::IpAddr },
+        "macaddr" if sqlx => quote::quote! { sqlx::types::mac_address::MacAddress },
+        "macaddr" => quote::quote! { eui48::MacAddress },
+        "hstore" if sqlx && !col.is_array => quote::quote! { sqlx::postgres::types::PgHstore },
+        "hstore" if !sqlx => {
+            quote::quote! { std::collections::HashMap<String, Option<String>> }
+        }
+        "oid" if sqlx => quote::quote! { sqlx::postgres::types::Oid },
+        "oid" => quote::quote! { u32 },
+        "int4range" if sqlx => range(quote::quote! { i32 }),
+        "int8range" if sqlx => range(quote::quote! { i64 }),
+        "numrange" if sqlx => range(types.decimal()),
+        "daterange" if sqlx => range(types.date()),
+        "tsrange" if sqlx => range(types.timestamp()),
+        "tstzrange" if sqlx => range(types.timestamptz()),
+        // sqlc could not infer the type, e.g. of a parameter only used in a function call.
+        "" => return Err("sqlc could not infer its type, cast it in the query".to_string()),
+        _ => {
+            let array = if col.is_array { "[]" } else { "" };
+            return Err(format!("{type_}{array} is not supported by {driver}"));
+        }
+    };
+
+    Ok(wrap_column_type(col, &ident))
+}
+
+/// Maps a MySQL type to the Rust type sqlx decodes it to, failing for types sqlx cannot decode.
+#[allow(clippy::match_same_arms)]
+fn convert_mysql_type(col: &plugin::Column, types: &TypeOptions) -> Result<TokenStream, String> {
+    let type_ = col
+        .r#type
+        .as_ref()
+        .expect("col type expected")
+        .name
+        .as_str();
+    let unsigned = col.unsigned;
+    let ident = match type_ {
+        "tinyint" if col.length == 1 => quote::quote! { bool },
+        "bool" | "boolean" => quote::quote! { bool },
+        "tinyint" if unsigned => quote::quote! { u8 },
+        "tinyint" => quote::quote! { i8 },
+        "smallint" if unsigned => quote::quote! { u16 },
+        "smallint" => quote::quote! { i16 },
+        "mediumint" | "int" | "integer" if unsigned => quote::quote! { u32 },
+        "mediumint" | "int" | "integer" => quote::quote! { i32 },
+        "bigint" if unsigned => quote::quote! { u64 },
+        "bigint" => quote::quote! { i64 },
+        "year" => quote::quote! { u16 },
+        "bit" => quote::quote! { u64 },
+        "float" => quote::quote! { f32 },
+        "double" | "double precision" | "real" => quote::quote! { f64 },
+        "decimal" | "dec" | "fixed" | "numeric" => types.decimal(),
+        "date" => types.date(),
+        "time" => types.time(),
+        "datetime" => types.timestamp(),
+        "timestamp" => types.mysql_timestamp(),
+        "char" | "varchar" | "tinytext" | "text" | "mediumtext" | "longtext" | "enum" | "set" => {
+            quote::quote! { String }
+        }
+        "binary" | "varbinary" | "tinyblob" | "blob" | "mediumblob" | "longblob" => {
+            quote::quote! { Vec<u8> }
+        }
+        "json" => quote::quote! { sqlx::types::Json<serde_json::Value> },
+        _ => return Err(format!("{type_} is not supported by sqlx")),
+    };
+
+    Ok(wrap_column_type(col, &ident))
+}
+
+/// Maps a `SQLite` declared type following the column affinity rules from
+/// <https://www.sqlite.org/datatype3.html#determination_of_column_affinity>.
+///
+/// Date, time and boolean declarations have no affinity of their own but are
+/// understood by sqlx, so they are matched before the affinity rules apply.
+///
+/// NUMERIC affinity columns store integers as INTEGER and other numbers as REAL,
+/// and sqlx decodes neither `i64` nor `f64` from both, so they need an override.
+fn convert_sqlite_type(col: &plugin::Column, types: &TypeOptions) -> Result<TokenStream, String> {
+    let type_ = col
+        .r#type
+        .as_ref()
+        .expect("col type expected")
+        .name
+        .to_lowercase();
+    let ident = match type_.as_str() {
+        "boolean" | "bool" => quote::quote! { bool },
+        "date" => types.date(),
+        "time" => types.time(),
+        "datetime" | "timestamp" => types.timestamp(),
+        "json" => quote::quote! { sqlx::types::Json<serde_json::Value> },
+        t if t.contains("int") => quote::quote! { i64 },
+        t if t.contains("char") || t.contains("clob") || t.contains("text") => {
+            quote::quote! { String }
+        }
+        t if t.contains("blob") || t.is_empty() => quote::quote! { Vec<u8> },
+        t if t.contains("real") || t.contains("floa") || t.contains("doub") => {
+            quote::quote! { f64 }
+        }
+        _ => {
+            return Err(format!(
+                "{type_} has NUMERIC affinity, whose values sqlx decodes as i64 or f64 depending \
+                 on how each one is stored"
+            ))
+        }
+    };
+
+    Ok(wrap_column_type(col, &ident))
+}
+
+/// `#[doc]` attributes for a SQL comment, one per line.
+fn doc_tokens(comment: &str) -> TokenStream {
+    let lines = comment.lines().map(|line| {
+        if line.is_empty() || line.starts_with(' ') {
+            line.to_string()
+        } else {
+            format!(" {line}")
+        }
+    });
+    quote::quote! { #(#[doc = #lines])* }
+}
+
+/// Whether a column is a `sqlc.embed()` table rather than a value.
+fn is_embed(col: &plugin::Column) -> bool {
+    col.embed_table
+        .as_ref()
+        .is_some_and(|table| !table.name.is_empty())
+}
+
+/// Number of array dimensions of a column, sqlc leaving `array_dims` unset on some array columns.
+fn array_dims(col: &plugin::Column) -> i32 {
+    if col.is_array {
+        col.array_dims.max(1)
+    } else {
+        0
+    }
+}
+
+/// Wraps a column's element type in `Vec` and `Option` as its array-ness and nullability require.
+/// Multi-dimensional arrays use `postgres_array::Array`, as `Vec<Vec<T>>` cannot be decoded.
+fn wrap_column_type(col: &plugin::Column, ident: &TokenStream) -> TokenStream {
+    let type_ = match array_dims(col) {
+        0 => ident.clone(),
+        1 => quote::quote! { Vec<#ident> },
+        _ => quote::quote! { postgres_array::Array<#ident> },
+    };
+    if col.not_null {
+        type_
+    } else {
+        quote::quote! { Option<#type_> }
+    }
+}
+
+/// Rust module declared for a generated file name.
+fn module_name(file_name: &str) -> Result<proc_macro2::Ident, String> {
+    file_name
+        .strip_suffix(".rs")
+        .and_then(|stem| syn::parse_str::<syn::Ident>(stem).ok())
+        .ok_or_else(|| format!("{file_name:?} is not a valid Rust module file name"))
+}
+
+/// Helper shared by the sqlx `:copyfrom` functions to write one binary `COPY` field.
+fn copy_in_field_tokens() -> TokenStream {
+    quote::quote! {
+        fn copy_in_field<'q, T>(
+            buf: &mut sqlx::postgres::PgArgumentBuffer,
+            value: T,
+        ) -> Result<(), sqlx::error::BoxDynError>
+        where
+            T: sqlx::Encode<'q, sqlx::Postgres>,
+        {
+            let offset = buf.len();
+            buf.extend_from_slice(&[0; 4]);
+            let len = match <T as sqlx::Encode<'q, sqlx::Postgres>>::encode(value, buf)? {
+                sqlx::encode::IsNull::No => i32::try_from(buf.len() - offset - 4)?,
+                sqlx::encode::IsNull::Yes => -1,
+            };
+            buf[offset..offset + 4].copy_from_slice(&len.to_be_bytes());
+            Ok(())
+        }
+    }
+}
+
+/// Like `copy_in_field`, for arrays whose element type differs from the one sqlx encodes, e.g.
+/// `varchar` elements that sqlx encodes as `text`. COPY checks the element type in the header.
+/// The element OID a `:copyfrom` array column needs written over the one sqlx encodes.
+fn copy_in_patched_oid(col: &plugin::Column) -> Option<u32> {
+    let type_name = col.r#type.as_ref().map_or("", |type_| type_.name.as_str());
+    col.is_array
+        .then(|| sqlx_copy_element_oid(type_name))
+        .flatten()
+}
+
+fn copy_in_array_field_tokens() -> TokenStream {
+    quote::quote! {
+        fn copy_in_array_field<'q, T>(
+            buf: &mut sqlx::postgres::PgArgumentBuffer,
+            value: T,
+            element_oid: u32,
+        ) -> Result<(), sqlx::error::BoxDynError>
+        where
+            T: sqlx::Encode<'q, sqlx::Postgres>,
+        {
+            let offset = buf.len();
+            copy_in_field(buf, value)?;
+            // Field length, dimensions and flags precede the element type.
+            if buf.len() >= offset + 16 {
+                buf[offset + 12..offset + 16].copy_from_slice(&element_oid.to_be_bytes());
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Whether sqlx encodes values of a PostgreSQL type with that type's own OID, which binary COPY
+/// requires of array elements and composite fields.
+fn sqlx_copy_native_type(type_name: &str) -> bool {
+    matches!(
+        type_name.strip_prefix("pg_catalog.").unwrap_or(type_name),
+        "bool"
+            | "boolean"
+            | "bytea"
+            | "char"
+            | "smallint"
+            | "int2"
+            | "integer"
+            | "int"
+            | "int4"
+            | "serial"
+            | "serial4"
+            | "bigint"
+            | "int8"
+            | "bigserial"
+            | "serial8"
+            | "smallserial"
+            | "serial2"
+            | "real"
+            | "float4"
+            | "float"
+            | "double precision"
+            | "float8"
+            | "numeric"
+            | "decimal"
+            | "money"
+            | "text"
+            | "uuid"
+            | "jsonb"
+            | "date"
+            | "time"
+            | "timetz"
+            | "timestamp"
+            | "timestamptz"
+            | "interval"
+            | "inet"
+            | "macaddr"
+            | "oid"
+            | "varbit"
+            | "bit varying"
+            | "int4range"
+            | "int8range"
+            | "numrange"
+            | "daterange"
+            | "tsrange"
+            | "tstzrange"
+    )
+}
+
+/// The element OID to write into binary COPY arrays of a PostgreSQL type that shares its Rust
+/// type with another, e.g. `varchar` with `text`.
+fn sqlx_copy_element_oid(type_name: &str) -> Option<u32> {
+    match type_name.strip_prefix("pg_catalog.").unwrap_or(type_name) {
+        "varchar" => Some(1043),
+        "bpchar" => Some(1042),
+        "name" => Some(19),
+        "cidr" => Some(650),
+        "bit" => Some(1560),
+        _ => None,
+    }
+}
+
+/// PostgreSQL `interval` for tokio-postgres, which has no type for it. Fields follow the binary
+/// representation, as months and days have no fixed length.
+fn tokio_postgres_interval_tokens() -> TokenStream {
+    quote::quote! {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+        pub struct PgInterval {
+            pub months: i32,
+            pub days: i32,
+            pub microseconds: i64,
+        }
+
+        impl<'a> postgres_types::FromSql<'a> for PgInterval {
+            fn from_sql(
+                _: &postgres_types::Type,
+                raw: &'a [u8],
+            ) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+                let raw: &[u8; 16] = raw.try_into()?;
+                let (microseconds, rest) = raw.split_at(8);
+                let (days, months) = rest.split_at(4);
+                Ok(Self {
+                    months: i32::from_be_bytes(months.try_into()?),
+                    days: i32::from_be_bytes(days.try_into()?),
+                    microseconds: i64::from_be_bytes(microseconds.try_into()?),
+                })
+            }
+
+            postgres_types::accepts!(INTERVAL);
+        }
+
+        impl postgres_types::ToSql for PgInterval {
+            fn to_sql(
+                &self,
+                _: &postgres_types::Type,
+                out: &mut bytes::BytesMut,
+            ) -> Result<postgres_types::IsNull, Box<dyn std::error::Error + Sync + Send>> {
+                out.extend_from_slice(&self.microseconds.to_be_bytes());
+                out.extend_from_slice(&self.days.to_be_bytes());
+                out.extend_from_slice(&self.months.to_be_bytes());
+                Ok(postgres_types::IsNull::No)
+            }
+
+            postgres_types::accepts!(INTERVAL);
+            postgres_types::to_sql_checked!();
+        }
+    }
+}
+
+/// Helper shared by functions with `sqlc.slice()` parameters to expand one slice placeholder.
+fn slice_placeholders_tokens() -> TokenStream {
+    quote::quote! {
+        fn slice_placeholders(len: usize) -> String {
+            if len == 0 {
+                "NULL".to_string()
+            } else {
+                vec!["?"; len].join(", ")
+            }
+        }
+    }
+}
 
 fn pretty_print_ts(ts: &proc_macro2::TokenStream) -> String {
     let syn_file = syn::parse2::<syn::File>(ts.clone())
@@ -388,3 +2684,1728 @@ fn pretty_print_ts(ts: &proc_macro2::TokenStream) -> String {
         .replace("\\n\")", "\\n\"\\n    )")
         .replace("\\n", "\n")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Tokens as a string, ignoring the spacing of punctuation like `>>`.
+    fn tokens(ts: &TokenStream) -> String {
+        ts.to_string().replace(' ', "")
+    }
+
+    fn column(name: &str, type_: &str) -> plugin::Column {
+        plugin::Column {
+            name: name.to_string(),
+            not_null: true,
+            r#type: Some(plugin::Identifier {
+                name: type_.to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn query(
+        name: &str,
+        cmd: &str,
+        columns: Vec<plugin::Column>,
+        params: Vec<plugin::Column>,
+    ) -> plugin::Query {
+        plugin::Query {
+            text: "SELECT 1".to_string(),
+            name: name.to_string(),
+            cmd: cmd.to_string(),
+            columns,
+            params: params
+                .into_iter()
+                .zip(1..)
+                .map(|(column, number)| plugin::Parameter {
+                    number,
+                    column: Some(column),
+                })
+                .collect(),
+            filename: "query.sql".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn generator(
+        engine: Engine,
+        driver: Driver,
+        schema: plugin::Schema,
+        queries: Vec<plugin::Query>,
+    ) -> Generator {
+        Generator {
+            req: plugin::GenerateRequest {
+                catalog: Some(plugin::Catalog {
+                    default_schema: "public".to_string(),
+                    schemas: vec![plugin::Schema {
+                        name: "public".to_string(),
+                        ..schema
+                    }],
+                    ..Default::default()
+                }),
+                queries,
+                ..Default::default()
+            },
+            driver,
+            engine,
+            overrides: Vec::new(),
+            rename: Rename::default(),
+            composite_types: Vec::new(),
+            types: TypeOptions::default(),
+            output: Output::default(),
+            structs: elsa::vec::FrozenVec::new(),
+        }
+    }
+
+    /// The generated file, without whitespace.
+    fn generate(mut gen: Generator) -> Result<String, String> {
+        let files = gen.generate()?;
+        let contents = String::from_utf8(files[0].contents.clone()).expect("utf-8 output");
+        Ok(contents.replace(char::is_whitespace, ""))
+    }
+
+    fn assert_generates(gen: Generator, expected: &[&str]) {
+        let generated = generate(gen).expect("generation should succeed");
+        for expected in expected {
+            let expected = expected.replace(char::is_whitespace, "");
+            assert!(
+                generated.contains(&expected),
+                "{expected} not in:\n{generated}"
+            );
+        }
+    }
+
+    fn postgres_type(
+        name: &str,
+        is_array: bool,
+        driver: Driver,
+        types: &TypeOptions,
+    ) -> Result<String, String> {
+        let col = plugin::Column {
+            not_null: true,
+            is_array,
+            array_dims: i32::from(is_array),
+            r#type: Some(plugin::Identifier {
+                name: name.to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        convert_postgres_type(&col, driver, types).map(|ts| tokens(&ts))
+    }
+
+    fn assert_postgres_types(driver: Driver, types: &TypeOptions, cases: &[(&str, TokenStream)]) {
+        for (name, expected) in cases {
+            let expected = tokens(expected);
+            for spelling in [name.to_string(), format!("pg_catalog.{name}")] {
+                assert_eq!(
+                    postgres_type(&spelling, false, driver, types),
+                    Ok(expected.clone()),
+                    "{spelling} with {driver}"
+                );
+            }
+        }
+    }
+
+    fn common_cases() -> Vec<(&'static str, TokenStream)> {
+        vec![
+            ("serial", quote::quote! { i32 }),
+            ("serial4", quote::quote! { i32 }),
+            ("int4", quote::quote! { i32 }),
+            ("integer", quote::quote! { i32 }),
+            ("bigserial", quote::quote! { i64 }),
+            ("serial8", quote::quote! { i64 }),
+            ("int8", quote::quote! { i64 }),
+            ("bigint", quote::quote! { i64 }),
+            ("smallserial", quote::quote! { i16 }),
+            ("serial2", quote::quote! { i16 }),
+            ("int2", quote::quote! { i16 }),
+            ("smallint", quote::quote! { i16 }),
+            ("float8", quote::quote! { f64 }),
+            ("double precision", quote::quote! { f64 }),
+            ("float4", quote::quote! { f32 }),
+            ("real", quote::quote! { f32 }),
+            ("numeric", quote::quote! { rust_decimal::Decimal }),
+            ("bool", quote::quote! { bool }),
+            ("boolean", quote::quote! { bool }),
+            ("char", quote::quote! { i8 }),
+            ("text", quote::quote! { String }),
+            ("varchar", quote::quote! { String }),
+            ("bpchar", quote::quote! { String }),
+            ("citext", quote::quote! { String }),
+            ("name", quote::quote! { String }),
+            ("bytea", quote::quote! { Vec<u8> }),
+            ("bit", quote::quote! { bit_vec::BitVec }),
+            ("varbit", quote::quote! { bit_vec::BitVec }),
+            ("bit varying", quote::quote! { bit_vec::BitVec }),
+            ("uuid", quote::quote! { uuid::Uuid }),
+            ("date", quote::quote! { chrono::NaiveDate }),
+            ("time", quote::quote! { chrono::NaiveTime }),
+            ("timestamp", quote::quote! { chrono::NaiveDateTime }),
+            (
+                "timestamptz",
+                quote::quote! { chrono::DateTime<chrono::Utc> },
+            ),
+        ]
+    }
+
+    #[test]
+    fn postgres_types_sqlx() {
+        let mut cases = common_cases();
+        cases.extend([
+            ("money", quote::quote! { sqlx::postgres::types::PgMoney }),
+            ("ltree", quote::quote! { sqlx::postgres::types::PgLTree }),
+            ("lquery", quote::quote! { sqlx::postgres::types::PgLQuery }),
+            (
+                "json",
+                quote::quote! { sqlx::types::Json<serde_json::Value> },
+            ),
+            (
+                "jsonb",
+                quote::quote! { sqlx::types::Json<serde_json::Value> },
+            ),
+            (
+                "timetz",
+                quote::quote! {
+                    sqlx::postgres::types::PgTimeTz<chrono::NaiveTime, chrono::FixedOffset>
+                },
+            ),
+            (
+                "interval",
+                quote::quote! { sqlx::postgres::types::PgInterval },
+            ),
+            ("inet", quote::quote! { sqlx::types::ipnetwork::IpNetwork }),
+            ("cidr", quote::quote! { sqlx::types::ipnetwork::IpNetwork }),
+            (
+                "macaddr",
+                quote::quote! { sqlx::types::mac_address::MacAddress },
+            ),
+            ("hstore", quote::quote! { sqlx::postgres::types::PgHstore }),
+            ("oid", quote::quote! { sqlx::postgres::types::Oid }),
+            (
+                "int4range",
+                quote::quote! { sqlx::postgres::types::PgRange<i32> },
+            ),
+            (
+                "int8range",
+                quote::quote! { sqlx::postgres::types::PgRange<i64> },
+            ),
+            (
+                "numrange",
+                quote::quote! { sqlx::postgres::types::PgRange<rust_decimal::Decimal> },
+            ),
+            (
+                "daterange",
+                quote::quote! { sqlx::postgres::types::PgRange<chrono::NaiveDate> },
+            ),
+            (
+                "tsrange",
+                quote::quote! { sqlx::postgres::types::PgRange<chrono::NaiveDateTime> },
+            ),
+            (
+                "tstzrange",
+                quote::quote! { sqlx::postgres::types::PgRange<chrono::DateTime<chrono::Utc>> },
+            ),
+        ]);
+        assert_postgres_types(Driver::Sqlx, &TypeOptions::default(), &cases);
+    }
+
+    #[test]
+    fn postgres_types_tokio_postgres() {
+        let mut cases = common_cases();
+        cases.extend([
+            ("ltree", quote::quote! { String }),
+            ("lquery", quote::quote! { String }),
+            ("ltxtquery", quote::quote! { String }),
+            ("json", quote::quote! { serde_json::Value }),
+            ("jsonb", quote::quote! { serde_json::Value }),
+            ("interval", quote::quote! { PgInterval }),
+            ("inet", quote::quote! { std::net::IpAddr }),
+            ("macaddr", quote::quote! { eui48::MacAddress }),
+            (
+                "hstore",
+                quote::quote! { std::collections::HashMap<String, Option<String>> },
+            ),
+            ("oid", quote::quote! { u32 }),
+        ]);
+        assert_postgres_types(Driver::TokioPostgres, &TypeOptions::default(), &cases);
+    }
+
+    #[test]
+    fn postgres_types_options() {
+        let types = TypeOptions {
+            time_crate: TimeCrate::Time,
+            decimal: DecimalCrate::Bigdecimal,
+            ..Default::default()
+        };
+        assert_postgres_types(
+            Driver::Sqlx,
+            &types,
+            &[
+                ("numeric", quote::quote! { sqlx::types::BigDecimal }),
+                ("date", quote::quote! { time::Date }),
+                ("time", quote::quote! { time::Time }),
+                (
+                    "timetz",
+                    quote::quote! { sqlx::postgres::types::PgTimeTz<time::Time, time::UtcOffset> },
+                ),
+                ("timestamp", quote::quote! { time::PrimitiveDateTime }),
+                ("timestamptz", quote::quote! { time::OffsetDateTime }),
+                (
+                    "tstzrange",
+                    quote::quote! { sqlx::postgres::types::PgRange<time::OffsetDateTime> },
+                ),
+                // sqlx decodes `interval` to neither `time::Duration` nor `chrono::Duration`.
+                (
+                    "interval",
+                    quote::quote! { sqlx::postgres::types::PgInterval },
+                ),
+            ],
+        );
+        assert_postgres_types(
+            Driver::TokioPostgres,
+            &TypeOptions {
+                decimal: DecimalCrate::RustDecimal,
+                ..types
+            },
+            &[
+                ("date", quote::quote! { time::Date }),
+                ("interval", quote::quote! { PgInterval }),
+            ],
+        );
+        let types = TypeOptions {
+            chrono_timestamptz: ChronoTimestamptz::FixedOffset,
+            ..Default::default()
+        };
+        assert_postgres_types(
+            Driver::Sqlx,
+            &types,
+            &[(
+                "timestamptz",
+                quote::quote! { chrono::DateTime<chrono::FixedOffset> },
+            )],
+        );
+    }
+
+    #[test]
+    fn postgres_types_unsupported() {
+        let types = TypeOptions::default();
+        for (name, is_array, driver) in [
+            ("pg_catalog.xml", false, Driver::Sqlx),
+            ("xml", false, Driver::TokioPostgres),
+            ("macaddr8", false, Driver::Sqlx),
+            ("ltxtquery", false, Driver::Sqlx),
+            ("hstore", true, Driver::Sqlx),
+            ("lquery", true, Driver::Sqlx),
+            ("money", false, Driver::TokioPostgres),
+            ("pg_catalog.timetz", false, Driver::TokioPostgres),
+            ("cidr", false, Driver::TokioPostgres),
+            ("tstzrange", false, Driver::TokioPostgres),
+            ("pg_catalog.int4multirange", false, Driver::Sqlx),
+            ("tsvector", false, Driver::Sqlx),
+            ("pg_catalog.tsquery", false, Driver::Sqlx),
+            ("point", false, Driver::Sqlx),
+            ("box", false, Driver::Sqlx),
+            ("polygon", false, Driver::Sqlx),
+            ("circle", false, Driver::TokioPostgres),
+            ("pg_lsn", false, Driver::Sqlx),
+            ("regclass", false, Driver::Sqlx),
+            ("pg_catalog.unknown_builtin", false, Driver::Sqlx),
+            ("vector", false, Driver::Sqlx),
+        ] {
+            assert!(
+                postgres_type(name, is_array, driver, &types).is_err(),
+                "{name} with {driver}"
+            );
+        }
+
+        let queries = vec![query(
+            "SetMetadata",
+            ":exec",
+            vec![],
+            vec![column("metadata", "")],
+        )];
+        let gen = generator(
+            Engine::Postgresql,
+            Driver::Sqlx,
+            plugin::Schema::default(),
+            queries,
+        );
+        assert_eq!(
+            generate(gen),
+            Err(
+                "SetMetadata: column metadata: sqlc could not infer its type, cast it in the query"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn postgres_types_wrapping() {
+        let types = TypeOptions::default();
+        assert_eq!(
+            postgres_type("pg_catalog.interval", true, Driver::Sqlx, &types),
+            Ok(tokens(
+                &quote::quote! { Vec<sqlx::postgres::types::PgInterval> }
+            ))
+        );
+        assert_eq!(
+            postgres_type("pg_catalog.bit", true, Driver::Sqlx, &types),
+            Ok(tokens(&quote::quote! { Vec<bit_vec::BitVec> }))
+        );
+        assert_eq!(
+            postgres_type("tstzrange", true, Driver::Sqlx, &types),
+            Ok(tokens(&quote::quote! {
+                Vec<sqlx::postgres::types::PgRange<chrono::DateTime<chrono::Utc>>>
+            }))
+        );
+    }
+
+    #[test]
+    fn bit_strings() {
+        let schema = || plugin::Schema {
+            tables: vec![plugin::Table {
+                rel: Some(identifier("", "flags")),
+                columns: vec![
+                    column("fixed", "pg_catalog.bit"),
+                    plugin::Column {
+                        not_null: false,
+                        ..column("mask", "varbit")
+                    },
+                    plugin::Column {
+                        is_array: true,
+                        array_dims: 1,
+                        ..column("history", "bit varying")
+                    },
+                    plugin::Column {
+                        not_null: false,
+                        is_array: true,
+                        array_dims: 1,
+                        ..column("masks", "pg_catalog.varbit")
+                    },
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        for driver in [Driver::Sqlx, Driver::TokioPostgres] {
+            assert_generates(
+                generator(Engine::Postgresql, driver, schema(), vec![]),
+                &["pub struct Flag {
+                    pub fixed: bit_vec::BitVec,
+                    pub mask: Option<bit_vec::BitVec>,
+                    pub history: Vec<bit_vec::BitVec>,
+                    pub masks: Option<Vec<bit_vec::BitVec>>,
+                }"],
+            );
+        }
+    }
+
+    #[test]
+    fn wrap_column_type_array_dims() {
+        let ident = quote::quote! { String };
+        for (is_array, array_dims, not_null, expected) in [
+            (false, 0, true, quote::quote! { String }),
+            (false, 0, false, quote::quote! { Option<String> }),
+            (true, 0, true, quote::quote! { Vec<String> }),
+            (true, 1, false, quote::quote! { Option<Vec<String>> }),
+            (
+                true,
+                2,
+                true,
+                quote::quote! { postgres_array::Array<String> },
+            ),
+            (
+                true,
+                3,
+                false,
+                quote::quote! { Option<postgres_array::Array<String>> },
+            ),
+        ] {
+            let col = plugin::Column {
+                not_null,
+                is_array,
+                array_dims,
+                ..Default::default()
+            };
+            assert_eq!(
+                tokens(&wrap_column_type(&col, &ident)),
+                tokens(&expected),
+                "{array_dims} dimensions"
+            );
+        }
+    }
+
+    fn mysql_type(name: &str, unsigned: bool, length: i32) -> String {
+        let col = plugin::Column {
+            not_null: true,
+            unsigned,
+            length,
+            r#type: Some(plugin::Identifier {
+                name: name.to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        convert_mysql_type(&col, &TypeOptions::default()).map_or_else(|e| e, |ts| tokens(&ts))
+    }
+
+    #[test]
+    fn mysql_types() {
+        for (name, unsigned, length, expected) in [
+            ("tinyint", false, 1, quote::quote! { bool }),
+            ("boolean", false, -1, quote::quote! { bool }),
+            ("tinyint", false, 4, quote::quote! { i8 }),
+            ("tinyint", true, 4, quote::quote! { u8 }),
+            ("smallint", false, -1, quote::quote! { i16 }),
+            ("smallint", true, -1, quote::quote! { u16 }),
+            ("mediumint", false, -1, quote::quote! { i32 }),
+            ("int", true, -1, quote::quote! { u32 }),
+            ("bigint", false, -1, quote::quote! { i64 }),
+            ("bigint", true, -1, quote::quote! { u64 }),
+            ("year", false, -1, quote::quote! { u16 }),
+            ("float", false, -1, quote::quote! { f32 }),
+            ("double", false, -1, quote::quote! { f64 }),
+            (
+                "decimal",
+                false,
+                -1,
+                quote::quote! { rust_decimal::Decimal },
+            ),
+            ("date", false, -1, quote::quote! { chrono::NaiveDate }),
+            (
+                "datetime",
+                false,
+                -1,
+                quote::quote! { chrono::NaiveDateTime },
+            ),
+            (
+                "timestamp",
+                false,
+                -1,
+                quote::quote! { chrono::DateTime<chrono::Utc> },
+            ),
+            ("varchar", false, 255, quote::quote! { String }),
+            ("enum", false, -1, quote::quote! { String }),
+            ("longblob", false, -1, quote::quote! { Vec<u8> }),
+            (
+                "json",
+                false,
+                -1,
+                quote::quote! { sqlx::types::Json<serde_json::Value> },
+            ),
+        ] {
+            assert_eq!(
+                mysql_type(name, unsigned, length),
+                tokens(&expected),
+                "{name} unsigned: {unsigned}, length: {length}"
+            );
+        }
+        for name in ["geometry", "point", "vector", ""] {
+            assert_eq!(
+                mysql_type(name, false, -1),
+                format!("{name} is not supported by sqlx")
+            );
+        }
+    }
+
+    #[test]
+    fn sqlite_types_affinity() {
+        for (name, expected) in [
+            ("INTEGER", quote::quote! { i64 }),
+            ("BIGINT", quote::quote! { i64 }),
+            ("UNSIGNED BIG INT", quote::quote! { i64 }),
+            ("VARCHAR(255)", quote::quote! { String }),
+            ("NCHAR(55)", quote::quote! { String }),
+            ("CLOB", quote::quote! { String }),
+            ("TEXT", quote::quote! { String }),
+            ("BLOB", quote::quote! { Vec<u8> }),
+            ("", quote::quote! { Vec<u8> }),
+            ("REAL", quote::quote! { f64 }),
+            ("DOUBLE PRECISION", quote::quote! { f64 }),
+            ("FLOAT", quote::quote! { f64 }),
+            ("BOOLEAN", quote::quote! { bool }),
+            ("DATE", quote::quote! { chrono::NaiveDate }),
+            ("TIME", quote::quote! { chrono::NaiveTime }),
+            ("DATETIME", quote::quote! { chrono::NaiveDateTime }),
+            ("TIMESTAMP", quote::quote! { chrono::NaiveDateTime }),
+            (
+                "JSON",
+                quote::quote! { sqlx::types::Json<serde_json::Value> },
+            ),
+        ] {
+            let col = plugin::Column {
+                not_null: true,
+                r#type: Some(plugin::Identifier {
+                    name: name.to_string(),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            };
+            assert_eq!(
+                convert_sqlite_type(&col, &TypeOptions::default()).map(|ts| tokens(&ts)),
+                Ok(tokens(&expected)),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn sqlite_numeric_affinity_needs_override() {
+        let price = || plugin::Column {
+            table: Some(identifier("", "products")),
+            ..column("price", "DECIMAL(10,5)")
+        };
+        let queries = || vec![query("GetPrice", ":one", vec![price()], vec![])];
+        let gen = generator(
+            Engine::Sqlite,
+            Driver::Sqlx,
+            plugin::Schema::default(),
+            queries(),
+        );
+        assert_eq!(
+            generate(gen).expect_err("NUMERIC affinity has no single Rust type"),
+            "GetPrice: column price: decimal(10,5) has NUMERIC affinity, whose values sqlx decodes \
+             as i64 or f64 depending on how each one is stored, add an override"
+        );
+
+        let mut gen = generator(
+            Engine::Sqlite,
+            Driver::Sqlx,
+            plugin::Schema::default(),
+            queries(),
+        );
+        gen.overrides = vec![Override {
+            rust_type: "f64".to_string(),
+            ..column_override("products.price")
+        }];
+        assert_generates(gen, &["Result<f64, sqlx::Error>"]);
+    }
+
+    #[test]
+    fn execresult_returns_query_result() {
+        let queries = || {
+            vec![query(
+                "DeleteAuthor",
+                ":execresult",
+                vec![],
+                vec![column("id", "bigint")],
+            )]
+        };
+        for (engine, driver, expected) in [
+            (
+                Engine::Postgresql,
+                Driver::Sqlx,
+                "Result<sqlx::postgres::PgQueryResult, sqlx::Error>",
+            ),
+            (
+                Engine::Mysql,
+                Driver::Sqlx,
+                "Result<sqlx::mysql::MySqlQueryResult, sqlx::Error>",
+            ),
+            (
+                Engine::Sqlite,
+                Driver::Sqlx,
+                "Result<sqlx::sqlite::SqliteQueryResult, sqlx::Error>",
+            ),
+            (
+                Engine::Postgresql,
+                Driver::TokioPostgres,
+                "Result<u64, tokio_postgres::Error>",
+            ),
+        ] {
+            let gen = generator(engine, driver, plugin::Schema::default(), queries());
+            assert_generates(gen, &[expected]);
+        }
+    }
+
+    fn copyfrom_query(params: Vec<plugin::Column>) -> plugin::Query {
+        plugin::Query {
+            insert_into_table: Some(plugin::Identifier {
+                name: "authors".to_string(),
+                ..Default::default()
+            }),
+            ..query("CreateAuthors", ":copyfrom", vec![], params)
+        }
+    }
+
+    #[test]
+    fn copyfrom_statement() {
+        let queries = vec![copyfrom_query(vec![
+            column("name", "text"),
+            column("bio", "text"),
+        ])];
+        let gen = generator(
+            Engine::Postgresql,
+            Driver::Sqlx,
+            plugin::Schema::default(),
+            queries,
+        );
+        assert_generates(
+            gen,
+            &[
+                r#"db.copy_in_raw("COPY \"authors\" (\"name\", \"bio\") FROM STDIN (FORMAT binary)")"#,
+                "rows: impl IntoIterator<Item = CreateAuthorsInfo>",
+            ],
+        );
+    }
+
+    #[test]
+    fn copyfrom_rejected_columns() {
+        let mut mood = column("moods", "mood");
+        mood.is_array = true;
+        mood.array_dims = 1;
+        let schema = plugin::Schema {
+            enums: vec![plugin::Enum {
+                name: "mood".to_string(),
+                vals: vec!["happy".to_string(), "sad".to_string()],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        for (schema, param) in [
+            (plugin::Schema::default(), column("data", "pg_catalog.json")),
+            (schema, mood),
+        ] {
+            let queries = vec![copyfrom_query(vec![column("name", "text"), param])];
+            let gen = generator(Engine::Postgresql, Driver::Sqlx, schema, queries);
+            assert!(generate(gen).is_err());
+        }
+
+        let queries = vec![copyfrom_query(vec![column("data", "json")])];
+        let gen = generator(
+            Engine::Postgresql,
+            Driver::TokioPostgres,
+            plugin::Schema::default(),
+            queries,
+        );
+        assert!(generate(gen).is_ok());
+    }
+
+    #[test]
+    fn copyfrom_array_element_types() {
+        let array = |name: &str, type_: &str| plugin::Column {
+            is_array: true,
+            array_dims: 1,
+            ..column(name, type_)
+        };
+        let queries = vec![copyfrom_query(vec![
+            array("tags", "pg_catalog.varchar"),
+            array("notes", "text"),
+        ])];
+        let gen = generator(
+            Engine::Postgresql,
+            Driver::Sqlx,
+            plugin::Schema::default(),
+            queries,
+        );
+        assert_generates(
+            gen,
+            &[
+                "copy_in_array_field(&mut buf, row.tags, 1043u32)",
+                "copy_in_field(&mut buf, row.notes)",
+                "buf[offset + 12..offset + 16].copy_from_slice(&element_oid.to_be_bytes());",
+            ],
+        );
+
+        let queries = vec![copyfrom_query(vec![array("names", "citext")])];
+        let gen = generator(
+            Engine::Postgresql,
+            Driver::Sqlx,
+            plugin::Schema::default(),
+            queries,
+        );
+        assert_eq!(
+            generate(gen),
+            Err("CreateAuthors: column names: citext[] cannot be copied with sqlx".to_string())
+        );
+    }
+
+    #[test]
+    fn copyfrom_composite_fields() {
+        let copy_point = |composite: CompositeType| {
+            let queries = vec![copyfrom_query(vec![column("p", "point3")])];
+            let mut gen = generator(
+                Engine::Postgresql,
+                Driver::Sqlx,
+                composite_schema(),
+                queries,
+            );
+            gen.composite_types = vec![composite];
+            generate(gen)
+        };
+        assert!(copy_point(point3()).is_ok());
+
+        let mut composite = point3();
+        composite.fields[2].db_type = "varchar[]".to_string();
+        assert_eq!(
+            copy_point(composite),
+            Err(
+                "CreateAuthors: column p: composite field tags of type varchar cannot be copied \
+                 with sqlx"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn copyfrom_requires_postgresql() {
+        let queries = vec![copyfrom_query(vec![column("name", "text")])];
+        let gen = generator(
+            Engine::Mysql,
+            Driver::Sqlx,
+            plugin::Schema::default(),
+            queries,
+        );
+        assert_eq!(
+            generate(gen),
+            Err("CreateAuthors: :copyfrom is only supported by the postgresql engine".to_string())
+        );
+    }
+
+    #[test]
+    fn execlastid() {
+        let queries = || {
+            vec![query(
+                "CreateAuthor",
+                ":execlastid",
+                vec![],
+                vec![column("name", "text")],
+            )]
+        };
+        let gen = generator(
+            Engine::Mysql,
+            Driver::Sqlx,
+            plugin::Schema::default(),
+            queries(),
+        );
+        assert_generates(gen, &["Result<u64, sqlx::Error>", ".last_insert_id()"]);
+        let gen = generator(
+            Engine::Sqlite,
+            Driver::Sqlx,
+            plugin::Schema::default(),
+            queries(),
+        );
+        assert_generates(gen, &["Result<i64, sqlx::Error>", ".last_insert_rowid()"]);
+        let gen = generator(
+            Engine::Postgresql,
+            Driver::Sqlx,
+            plugin::Schema::default(),
+            queries(),
+        );
+        assert!(generate(gen).is_err_and(|e| e.starts_with("CreateAuthor: :execlastid")));
+    }
+
+    #[test]
+    fn one_optional() {
+        let id = || plugin::Column {
+            table: Some(identifier("", "authors")),
+            ..column("id", "int8")
+        };
+        let queries = || {
+            vec![query(
+                "GetAuthor",
+                ":one",
+                vec![id(), column("name", "text")],
+                vec![id()],
+            )]
+        };
+        let gen = generator(
+            Engine::Postgresql,
+            Driver::Sqlx,
+            plugin::Schema::default(),
+            queries(),
+        );
+        assert_generates(
+            gen,
+            &["pub async fn get_author_optional<'e, E>(db: E, id: i64,)
+                -> Result<Option<GetAuthorRow>, sqlx::Error>
+                where E: sqlx::Executor<'e, Database = sqlx::Postgres>,
+                {
+                    let rec: Option<GetAuthorRow> = sqlx::query_as(GET_AUTHOR)
+                    .bind(id)
+                    .fetch_optional(db)
+                    .await?;
+                    Ok(rec)
+                }"],
+        );
+        let gen = generator(
+            Engine::Postgresql,
+            Driver::TokioPostgres,
+            plugin::Schema::default(),
+            queries(),
+        );
+        assert_generates(
+            gen,
+            &["pub async fn get_author_optional(client: &impl tokio_postgres::GenericClient, id: i64,)
+                -> Result<Option<GetAuthorRow>, tokio_postgres::Error> {
+                    let row = client.query_opt(GET_AUTHOR, &[&id]).await?;
+                    row.as_ref().map(GetAuthorRow::try_from).transpose()
+                }"],
+        );
+    }
+
+    #[test]
+    fn many_stream() {
+        let queries = vec![query(
+            "ListNames",
+            ":many",
+            vec![column("name", "text")],
+            vec![column("bio", "text")],
+        )];
+        let gen = generator(
+            Engine::Postgresql,
+            Driver::Sqlx,
+            plugin::Schema::default(),
+            queries,
+        );
+        assert_generates(
+            gen,
+            &["pub fn list_names_stream<'e, E>(db: E, bio: String,)
+                -> impl futures::Stream<Item = Result<String, sqlx::Error>> + 'e
+                where E: sqlx::Executor<'e, Database = sqlx::Postgres> + 'e,
+                {
+                    sqlx::query_scalar(LIST_NAMES).bind(bio).fetch(db)
+                }"],
+        );
+    }
+
+    #[test]
+    fn batch_commands() {
+        let queries = vec![
+            query(
+                "DeleteAuthors",
+                ":batchexec",
+                vec![],
+                vec![column("id", "bigint")],
+            ),
+            query(
+                "GetAuthorNames",
+                ":batchone",
+                vec![column("name", "text")],
+                vec![column("id", "bigint")],
+            ),
+            query(
+                "ListAuthorNames",
+                ":batchmany",
+                vec![column("name", "text")],
+                vec![column("bio", "text")],
+            ),
+        ];
+        let gen = generator(
+            Engine::Postgresql,
+            Driver::Sqlx,
+            plugin::Schema::default(),
+            queries,
+        );
+        assert_generates(
+            gen,
+            &[
+                "batch: impl IntoIterator<Item = DeleteAuthorsInfo>",
+                "-> Result<Vec<Result<(), sqlx::Error>>, sqlx::Error>",
+                "let result: Result<String, sqlx::Error> = sqlx::query_scalar(GET_AUTHOR_NAMES)",
+                "-> Result<Vec<Result<Vec<String>, sqlx::Error>>, sqlx::Error>",
+                ".fetch_all(&mut *db).await",
+            ],
+        );
+    }
+
+    fn variants(values: &[&str]) -> Vec<(String, String)> {
+        values
+            .iter()
+            .map(|value| (ident::to_upper_camel(value), (*value).to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn enum_rename_all_strategies() {
+        assert_eq!(
+            enum_rename_all(&variants(&["available", "running"])),
+            Some("lowercase")
+        );
+        assert_eq!(
+            enum_rename_all(&variants(&["on_hold", "running"])),
+            Some("snake_case")
+        );
+        assert_eq!(
+            enum_rename_all(&variants(&["OPEN", "CLOSED"])),
+            Some("UPPERCASE")
+        );
+        assert_eq!(
+            enum_rename_all(&variants(&["ON_HOLD", "OPEN"])),
+            Some("SCREAMING_SNAKE_CASE")
+        );
+        assert_eq!(
+            enum_rename_all(&variants(&["on-hold", "open"])),
+            Some("kebab-case")
+        );
+        assert_eq!(enum_rename_all(&variants(&["Weird Value", "open"])), None);
+        assert_eq!(enum_rename_all(&variants(&["open", "CLOSED"])), None);
+    }
+
+    #[test]
+    fn doc_comments() {
+        let schema = plugin::Schema {
+            tables: vec![plugin::Table {
+                rel: Some(identifier("", "authors")),
+                columns: vec![plugin::Column {
+                    comment: "Pen name.".to_string(),
+                    ..column("name", "text")
+                }],
+                comment: "People who write.\nAnd sometimes edit.".to_string(),
+            }],
+            enums: vec![plugin::Enum {
+                name: "mood".to_string(),
+                vals: vec!["happy".to_string()],
+                comment: "How an author feels.".to_string(),
+            }],
+            ..Default::default()
+        };
+        let queries = vec![plugin::Query {
+            comments: vec![" Lists every name.".to_string(), " Sorted.".to_string()],
+            ..query("ListNames", ":many", vec![column("name", "text")], vec![])
+        }];
+        let mut gen = generator(Engine::Postgresql, Driver::Sqlx, schema, queries);
+        let files = gen.generate().expect("generation should succeed");
+        let contents = String::from_utf8(files[0].contents.clone()).expect("utf-8 output");
+        for expected in [
+            "/// How an author feels.\n#[derive(",
+            "/// People who write.\n/// And sometimes edit.\n#[derive(",
+            "    /// Pen name.\n    pub name: String,",
+            "/// Lists every name.\n/// Sorted.\n///\n/// Generated from `query.sql` (`:many`).\n\
+             pub async fn list_names<",
+        ] {
+            assert!(
+                contents.contains(expected),
+                "{expected} not in:\n{contents}"
+            );
+        }
+    }
+
+    #[test]
+    fn enum_variant_names() {
+        let enum_gen = |vals: &[&str]| {
+            let schema = plugin::Schema {
+                enums: vec![plugin::Enum {
+                    name: "mood".to_string(),
+                    vals: vals.iter().map(ToString::to_string).collect(),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            };
+            generator(Engine::Postgresql, Driver::Sqlx, schema, vec![])
+        };
+        for val in ["", "-"] {
+            assert_eq!(
+                generate(enum_gen(&["happy", val])),
+                Err(format!(
+                    "enum mood: value {val:?} does not make an identifier, name it in rename.values"
+                ))
+            );
+        }
+        assert_eq!(
+            generate(enum_gen(&["a-b", "a_b"])),
+            Err(
+                r#"enum mood: values "a-b" and "a_b" are both named AB, rename one in rename.values"#
+                    .to_string()
+            )
+        );
+
+        let mut gen = enum_gen(&["-", "a-b", "a_b"]);
+        gen.rename.values = [("-", "None"), ("mood.a_b", "ASnake")]
+            .into_iter()
+            .map(|(from, to)| (from.to_string(), to.to_string()))
+            .collect();
+        assert_generates(
+            gen,
+            &[
+                r#"#[sqlx(rename = "-")] None, #[sqlx(rename = "a-b")] AB, #[sqlx(rename = "a_b")] ASnake,"#,
+            ],
+        );
+    }
+
+    #[test]
+    fn enums_per_driver() {
+        let schema = || plugin::Schema {
+            enums: vec![plugin::Enum {
+                name: "job_state".to_string(),
+                vals: vec!["available".to_string(), "Weird Value".to_string()],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let gen = generator(Engine::Postgresql, Driver::Sqlx, schema(), vec![]);
+        assert_generates(
+            gen,
+            &[
+                r#"#[sqlx(type_name = "job_state")] pub enum JobState"#,
+                r#"#[sqlx(rename = "available")] Available"#,
+                r#"#[sqlx(rename = "Weird Value")] WeirdValue"#,
+            ],
+        );
+        let gen = generator(Engine::Postgresql, Driver::TokioPostgres, schema(), vec![]);
+        assert_generates(
+            gen,
+            &[
+                r#"#[postgres(name = "job_state")] pub enum JobState"#,
+                r#"#[postgres(name = "Weird Value")] WeirdValue"#,
+            ],
+        );
+    }
+
+    fn composite_schema() -> plugin::Schema {
+        plugin::Schema {
+            composite_types: vec![plugin::CompositeType {
+                name: "point3".to_string(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn point3() -> CompositeType {
+        let field = |name: &str, db_type: &str, not_null| CompositeField {
+            name: name.to_string(),
+            db_type: db_type.to_string(),
+            not_null,
+        };
+        CompositeType {
+            name: "point3".to_string(),
+            fields: vec![
+                field("x", "pg_catalog.float8", true),
+                field("y", "float8", true),
+                field("tags", "text[]", false),
+            ],
+        }
+    }
+
+    #[test]
+    fn composite_types() {
+        let queries = || {
+            vec![query(
+                "GetPoint",
+                ":one",
+                vec![column("p", "point3")],
+                vec![],
+            )]
+        };
+        let mut gen = generator(
+            Engine::Postgresql,
+            Driver::Sqlx,
+            composite_schema(),
+            queries(),
+        );
+        gen.composite_types = vec![point3()];
+        assert_generates(
+            gen,
+            &[
+                r#"#[derive(Debug, Clone, sqlx::Type)] #[sqlx(type_name = "point3")]
+                pub struct Point3 { pub x: f64, pub y: f64, pub tags: Option<Vec<String>>, }"#,
+                "-> Result<Point3, sqlx::Error>",
+            ],
+        );
+        let mut gen = generator(
+            Engine::Postgresql,
+            Driver::TokioPostgres,
+            composite_schema(),
+            queries(),
+        );
+        gen.composite_types = vec![point3()];
+        assert_generates(
+            gen,
+            &[r#"#[postgres(name = "point3")] pub struct Point3 {
+                #[postgres(name = "x")] pub x: f64"#],
+        );
+    }
+
+    #[test]
+    fn composite_types_without_fields() {
+        let queries = vec![query(
+            "GetPoint",
+            ":one",
+            vec![column("p", "point3")],
+            vec![],
+        )];
+        let gen = generator(
+            Engine::Postgresql,
+            Driver::Sqlx,
+            composite_schema(),
+            queries,
+        );
+        let err = generate(gen).expect_err("point3 has no fields");
+        assert!(
+            err.contains("composite type \"point3\" has no known fields"),
+            "{err}"
+        );
+
+        let mut gen = generator(Engine::Postgresql, Driver::Sqlx, composite_schema(), vec![]);
+        gen.composite_types = vec![CompositeType {
+            name: "point4".to_string(),
+            ..point3()
+        }];
+        let err = generate(gen).expect_err("point4 is not in the catalog");
+        assert!(err.contains("\"point4\" is not a composite type"), "{err}");
+    }
+
+    fn db_type_override(db_type: &str, nullable: bool) -> Override {
+        Override {
+            db_type: Some(db_type.to_string()),
+            column: None,
+            rust_type: "T".to_string(),
+            nullable,
+        }
+    }
+
+    fn column_override(column: &str) -> Override {
+        Override {
+            db_type: None,
+            column: Some(column.to_string()),
+            rust_type: "T".to_string(),
+            nullable: false,
+        }
+    }
+
+    fn identifier(schema: &str, name: &str) -> plugin::Identifier {
+        plugin::Identifier {
+            schema: schema.to_string(),
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn override_matches_db_type() {
+        let numeric = identifier("", "numeric");
+        assert!(db_type_override("numeric", false).matches_db_type(&numeric, true, 0));
+        assert!(db_type_override("pg_catalog.numeric", false).matches_db_type(&numeric, true, 0));
+        assert!(db_type_override("numeric", false).matches_db_type(
+            &identifier("", "pg_catalog.numeric"),
+            true,
+            0
+        ));
+        assert!(!db_type_override("numeric", false).matches_db_type(&numeric, false, 0));
+        assert!(db_type_override("numeric", true).matches_db_type(&numeric, false, 0));
+        assert!(!db_type_override("numeric", true).matches_db_type(&numeric, true, 0));
+        assert!(!db_type_override("int4", false).matches_db_type(&numeric, true, 0));
+
+        let mood = identifier("extra", "mood");
+        assert!(db_type_override("extra.mood", false).matches_db_type(&mood, true, 0));
+        assert!(db_type_override("mood", false).matches_db_type(&mood, true, 0));
+        assert!(!db_type_override("public.mood", false).matches_db_type(&mood, true, 0));
+
+        assert!(db_type_override("numeric", false).matches_db_type(&numeric, true, 2));
+        assert!(db_type_override("numeric[][]", false).matches_db_type(&numeric, true, 2));
+        assert!(!db_type_override("numeric[][]", false).matches_db_type(&numeric, true, 1));
+        assert!(!db_type_override("numeric[][]", false).matches_db_type(&numeric, true, 0));
+    }
+
+    #[test]
+    fn override_matches_column() {
+        let col = |schema: &str, table: &str| plugin::Column {
+            table: Some(identifier(schema, table)),
+            ..column("args", "jsonb")
+        };
+        assert!(column_override("river_job.args").matches_column(&col("", "river_job"), "public"));
+        assert!(column_override("public.river_job.args")
+            .matches_column(&col("", "river_job"), "public"));
+        assert!(column_override("queue.river_job.args")
+            .matches_column(&col("queue", "river_job"), "public"));
+        assert!(!column_override("public.river_job.args")
+            .matches_column(&col("queue", "river_job"), "public"));
+        assert!(!column_override("river_job.args").matches_column(&col("", "other"), "public"));
+        assert!(!column_override("river_job.kind").matches_column(&col("", "river_job"), "public"));
+        assert!(
+            !column_override("river_job.args").matches_column(&column("args", "jsonb"), "public")
+        );
+
+        let renamed = plugin::Column {
+            name: "job_args".to_string(),
+            original_name: "args".to_string(),
+            ..col("", "river_job")
+        };
+        assert!(column_override("river_job.args").matches_column(&renamed, "public"));
+    }
+
+    #[test]
+    fn override_matches_insert_param() {
+        let schema = plugin::Schema {
+            tables: vec![plugin::Table {
+                rel: Some(identifier("", "river_job")),
+                columns: vec![column("args", "jsonb")],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        // `@args::jsonb` has no table.
+        let mut insert = query("JobInsert", ":exec", vec![], vec![column("args", "jsonb")]);
+        insert.insert_into_table = Some(identifier("", "river_job"));
+        let mut gen = generator(Engine::Postgresql, Driver::Sqlx, schema, vec![insert]);
+        gen.overrides = vec![Override {
+            rust_type: "sqlx::types::Json<JobArgs>".to_string(),
+            ..column_override("river_job.args")
+        }];
+        assert_generates(gen, &["args: sqlx::types::Json<JobArgs>"]);
+    }
+
+    #[test]
+    fn rename_per_kind() {
+        let schema = plugin::Schema {
+            tables: vec![plugin::Table {
+                rel: Some(identifier("", "river_job")),
+                columns: vec![column("id", "int8")],
+                ..Default::default()
+            }],
+            enums: vec![plugin::Enum {
+                name: "status".to_string(),
+                vals: vec!["active".to_string()],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let embed = plugin::Column {
+            embed_table: Some(identifier("", "river_job")),
+            ..column("river_job", "")
+        };
+        let queries = vec![query(
+            "JobGet",
+            ":one",
+            vec![embed, column("status", "status")],
+            vec![],
+        )];
+        let mut gen = generator(Engine::Postgresql, Driver::Sqlx, schema, queries);
+        let map = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(from, to)| ((*from).to_string(), (*to).to_string()))
+                .collect()
+        };
+        gen.rename = Rename {
+            types: map(&[("river_job", "Job"), ("JobGetRow", "JobWithStatus")]),
+            columns: map(&[("status", "state")]),
+            values: map(&[("status.active", "Running")]),
+            queries: map(&[("JobGet", "job_by_id")]),
+        };
+        assert_generates(
+            gen,
+            &[
+                "pub struct Job {",
+                r#"pub enum Status { #[sqlx(rename = "active")] Running, }"#,
+                "pub struct JobWithStatus { pub river_job: Job,",
+                "pub state: Status",
+                "state: row.try_get(1)?",
+                "pub async fn job_by_id",
+            ],
+        );
+    }
+
+    #[test]
+    fn table_model_matches_columns() {
+        let author = |name: &str| plugin::Column {
+            table: Some(identifier("", "authors")),
+            ..column(name, "text")
+        };
+        let schema = || plugin::Schema {
+            tables: vec![plugin::Table {
+                rel: Some(identifier("", "authors")),
+                columns: vec![
+                    column("id", "text"),
+                    column("name", "text"),
+                    column("bio", "text"),
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let gen = |cols: Vec<plugin::Column>| {
+            let queries = vec![query("GetAuthor", ":one", cols, vec![])];
+            generator(Engine::Postgresql, Driver::Sqlx, schema(), queries)
+        };
+
+        assert_generates(
+            gen(vec![author("id"), author("name"), author("bio")]),
+            &["-> Result<Author, sqlx::Error>"],
+        );
+        let cross_table = plugin::Column {
+            table: Some(identifier("", "books")),
+            ..column("bio", "text")
+        };
+        for cols in [
+            vec![author("name"), author("id"), author("bio")],
+            vec![author("id"), author("name")],
+            vec![author("id"), author("name"), cross_table],
+        ] {
+            assert_generates(
+                gen(cols),
+                &[
+                    "pub struct GetAuthorRow",
+                    "-> Result<GetAuthorRow, sqlx::Error>",
+                ],
+            );
+        }
+    }
+
+    #[test]
+    fn embeds_decode_by_index() {
+        let table = |name: &str, columns: Vec<plugin::Column>| plugin::Table {
+            rel: Some(identifier("", name)),
+            columns,
+            ..Default::default()
+        };
+        let schema = || plugin::Schema {
+            tables: vec![
+                table(
+                    "authors",
+                    vec![column("id", "int8"), column("name", "text")],
+                ),
+                table("books", vec![column("id", "int8"), column("title", "text")]),
+            ],
+            ..Default::default()
+        };
+        let embed = |name: &str| plugin::Column {
+            embed_table: Some(identifier("", name)),
+            ..column(name, "")
+        };
+        let queries = || {
+            vec![query(
+                "GetBook",
+                ":one",
+                vec![column("rank", "int4"), embed("authors"), embed("books")],
+                vec![],
+            )]
+        };
+        let gen = generator(Engine::Postgresql, Driver::Sqlx, schema(), queries());
+        assert_generates(
+            gen,
+            &[
+                "pub struct GetBookRow { pub rank: i32, pub authors: Author, pub books: Book, }",
+                "impl sqlx::FromRow<'_, sqlx::postgres::PgRow> for GetBookRow",
+                "Ok(Self {
+                    rank: row.try_get(0)?,
+                    authors: Author { id: row.try_get(1)?, name: row.try_get(2)?, },
+                    books: Book { id: row.try_get(3)?, title: row.try_get(4)?, },
+                })",
+            ],
+        );
+        let gen = generator(
+            Engine::Postgresql,
+            Driver::TokioPostgres,
+            schema(),
+            queries(),
+        );
+        assert_generates(
+            gen,
+            &[
+                "impl TryFrom<&tokio_postgres::Row> for GetBookRow",
+                "books: Book { id: row.try_get(3)?, title: row.try_get(4)?, }",
+            ],
+        );
+    }
+
+    fn slice_query(cmd: &str) -> plugin::Query {
+        let ids = plugin::Column {
+            is_sqlc_slice: true,
+            ..column("ids", "bigint")
+        };
+        plugin::Query {
+            text: "SELECT id FROM authors WHERE id IN (/*SLICE:ids*/?) AND name = ?".to_string(),
+            ..query(
+                "ListAuthors",
+                cmd,
+                vec![column("id", "bigint")],
+                vec![ids, column("name", "text")],
+            )
+        }
+    }
+
+    #[test]
+    fn slice_placeholders_expand() {
+        let gen = generator(
+            Engine::Mysql,
+            Driver::Sqlx,
+            plugin::Schema::default(),
+            vec![slice_query(":many")],
+        );
+        assert_generates(
+            gen,
+            &[
+                "ids: &[i64], name: String,",
+                r#"let sql = LIST_AUTHORS.replace("/*SLICE:ids*/?", &slice_placeholders(ids.len()));"#,
+                "let mut query = sqlx::query_scalar::<sqlx::MySql, i64>(&sql);",
+                "for value in ids { query = query.bind(value); }",
+                "query = query.bind(name);",
+                r#"fn slice_placeholders(len: usize) -> String {
+                    if len == 0 { "NULL".to_string() } else { vec!["?"; len].join(", ") }
+                }"#,
+            ],
+        );
+    }
+
+    #[test]
+    fn slices_rejected_in_batches() {
+        for cmd in [":batchexec", ":batchmany", ":batchone", ":copyfrom"] {
+            let gen = generator(
+                Engine::Postgresql,
+                Driver::Sqlx,
+                plugin::Schema::default(),
+                vec![slice_query(cmd)],
+            );
+            let err = generate(gen).expect_err("slices cannot be batched");
+            assert_eq!(
+                err,
+                format!("ListAuthors: sqlc.slice() is not supported by {cmd}")
+            );
+        }
+    }
+
+    /// Generated files by name, without whitespace.
+    fn generate_files(mut gen: Generator) -> Result<Vec<(String, String)>, String> {
+        Ok(gen
+            .generate()?
+            .into_iter()
+            .map(|file| {
+                let contents = String::from_utf8(file.contents).expect("utf-8 output");
+                (file.name, contents.replace(char::is_whitespace, ""))
+            })
+            .collect())
+    }
+
+    #[test]
+    fn split_files() {
+        let schema = plugin::Schema {
+            tables: vec![plugin::Table {
+                rel: Some(identifier("", "river_job")),
+                columns: vec![column("id", "int8")],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let in_file = |name: &str, filename: &str| plugin::Query {
+            filename: filename.to_string(),
+            ..query(name, ":exec", vec![], vec![])
+        };
+        let queries = vec![
+            in_file("JobDelete", "river_job.sql"),
+            in_file("QueuePause", "queries/river-queue.sql"),
+            in_file("JobRetry", "river_job.sql"),
+        ];
+        let mut gen = generator(Engine::Postgresql, Driver::Sqlx, schema, queries);
+        gen.output.split_files = true;
+        gen.output.files_suffix = "_queries".to_string();
+        let files = generate_files(gen).expect("generation should succeed");
+        let names: Vec<_> = files.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "models.rs",
+                "river_job_queries.rs",
+                "river_queue_queries.rs",
+                "mod.rs"
+            ]
+        );
+        assert!(files[0].1.contains("pubstructRiverJob{"), "{}", files[0].1);
+        assert!(
+            files[1].1.contains("usesuper::models::*;"),
+            "{}",
+            files[1].1
+        );
+        assert!(files[1].1.contains("pubasyncfnjob_delete"));
+        assert!(files[1].1.contains("pubasyncfnjob_retry"));
+        assert!(!files[1].1.contains("queue_pause"));
+        assert!(files[2].1.contains("pubasyncfnqueue_pause"));
+        assert_eq!(
+            files[3].1,
+            "//!Thisfileis@generatedbysqlc-gen-rust.\
+             pubmodmodels;pubmodriver_job_queries;pubmodriver_queue_queries;\
+             pubusemodels::*;pubuseriver_job_queries::*;pubuseriver_queue_queries::*;"
+        );
+    }
+
+    #[test]
+    fn split_files_module_names() {
+        let queries = || {
+            vec![
+                plugin::Query {
+                    filename: "jobs.sql".to_string(),
+                    ..query("JobDelete", ":exec", vec![], vec![])
+                },
+                plugin::Query {
+                    filename: "sub/jobs.sql".to_string(),
+                    ..query("JobRetry", ":exec", vec![], vec![])
+                },
+            ]
+        };
+        let mut gen = generator(
+            Engine::Postgresql,
+            Driver::Sqlx,
+            plugin::Schema::default(),
+            queries(),
+        );
+        gen.output.split_files = true;
+        assert_eq!(
+            generate_files(gen).expect_err("jobs is generated twice"),
+            "sub/jobs.sql: module jobs is generated more than once"
+        );
+
+        let mut gen = generator(
+            Engine::Postgresql,
+            Driver::Sqlx,
+            plugin::Schema::default(),
+            queries(),
+        );
+        gen.output.split_files = true;
+        gen.output.models_file_name = "models.txt".to_string();
+        assert_eq!(
+            generate_files(gen).expect_err("models.txt is not a module"),
+            "\"models.txt\" is not a valid Rust module file name"
+        );
+    }
+
+    #[test]
+    fn multi_dimensional_arrays_with_overrides() {
+        let matrix = || plugin::Column {
+            is_array: true,
+            array_dims: 2,
+            table: Some(identifier("", "grids")),
+            ..column("cells", "int4")
+        };
+        let queries = || vec![query("GetCells", ":one", vec![matrix()], vec![])];
+        let gen = generator(
+            Engine::Postgresql,
+            Driver::Sqlx,
+            plugin::Schema::default(),
+            queries(),
+        );
+        let err = generate(gen).expect_err("sqlx only decodes one dimension");
+        assert!(
+            err.contains("2-dimensional arrays are not supported by sqlx"),
+            "{err}"
+        );
+
+        let mut gen = generator(
+            Engine::Postgresql,
+            Driver::Sqlx,
+            plugin::Schema::default(),
+            queries(),
+        );
+        gen.overrides = vec![Override {
+            rust_type: "crate::Matrix".to_string(),
+            ..column_override("grids.cells")
+        }];
+        assert_generates(gen, &["Result<crate::Matrix, sqlx::Error>"]);
+
+        let mut gen = generator(
+            Engine::Postgresql,
+            Driver::Sqlx,
+            plugin::Schema::default(),
+            queries(),
+        );
+        gen.overrides = vec![Override {
+            rust_type: "crate::Matrix".to_string(),
+            ..db_type_override("int4[][]", false)
+        }];
+        assert_generates(gen, &["Result<crate::Matrix, sqlx::Error>"]);
+
+        let mut gen = generator(
+            Engine::Postgresql,
+            Driver::Sqlx,
+            plugin::Schema::default(),
+            queries(),
+        );
+        gen.overrides = vec![Override {
+            rust_type: "crate::UserId".to_string(),
+            ..db_type_override("int4", false)
+        }];
+        let err = generate(gen).expect_err("a scalar override does not make a 2-dimensional type");
+        assert!(
+            err.contains("2-dimensional arrays are not supported by sqlx"),
+            "{err}"
+        );
+
+        let mut gen = generator(
+            Engine::Postgresql,
+            Driver::TokioPostgres,
+            plugin::Schema::default(),
+            queries(),
+        );
+        gen.overrides = vec![Override {
+            rust_type: "Cell".to_string(),
+            ..column_override("grids.cells")
+        }];
+        assert_generates(gen, &["postgres_array::Array<Cell>"]);
+
+        let mut gen = generator(
+            Engine::Postgresql,
+            Driver::TokioPostgres,
+            plugin::Schema::default(),
+            queries(),
+        );
+        gen.overrides = vec![Override {
+            rust_type: "crate::Matrix".to_string(),
+            ..db_type_override("int4[][]", false)
+        }];
+        assert_generates(gen, &["Result<crate::Matrix, tokio_postgres::Error>"]);
+    }
+
+    #[test]
+    fn tokio_postgres_commands() {
+        let id = || plugin::Column {
+            table: Some(identifier("", "authors")),
+            ..column("id", "int8")
+        };
+        let queries = vec![
+            query(
+                "GetAuthor",
+                ":one",
+                vec![id(), column("name", "text")],
+                vec![id()],
+            ),
+            query("ListNames", ":many", vec![column("name", "text")], vec![]),
+            query("DeleteAuthor", ":exec", vec![], vec![id()]),
+        ];
+        let gen = generator(
+            Engine::Postgresql,
+            Driver::TokioPostgres,
+            plugin::Schema::default(),
+            queries,
+        );
+        assert_generates(
+            gen,
+            &[
+                "pub async fn get_author(client: &impl tokio_postgres::GenericClient, id: i64,)
+                -> Result<GetAuthorRow, tokio_postgres::Error> {
+                    let row = client.query_one(GET_AUTHOR, &[&id]).await?;
+                    GetAuthorRow::try_from(&row)
+                }",
+                "pub async fn list_names(client: &impl tokio_postgres::GenericClient,)
+                -> Result<Vec<String>, tokio_postgres::Error> {
+                    let rows = client.query(LIST_NAMES, &[]).await?;
+                    rows.iter().map(|row| row.try_get(0)).collect()
+                }",
+                "pub async fn delete_author(client: &impl tokio_postgres::GenericClient, id: i64,)
+                -> Result<(), tokio_postgres::Error> {
+                    client.execute(DELETE_AUTHOR, &[&id]).await?;
+                    Ok(())
+                }",
+            ],
+        );
+    }
+
+    #[test]
+    fn tokio_postgres_requires_postgresql() {
+        for engine in [Engine::Mysql, Engine::Sqlite] {
+            let gen = generator(
+                engine,
+                Driver::TokioPostgres,
+                plugin::Schema::default(),
+                vec![],
+            );
+            assert_eq!(
+                generate(gen).expect_err("tokio-postgres only talks to PostgreSQL"),
+                format!("driver tokio-postgres does not support the {engine} engine")
+            );
+        }
+    }
+}
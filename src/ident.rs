@@ -1,5 +1,5 @@
 /// Utilities for sanitizing and converting identifiers taken from prost-build
-use heck::{ToSnakeCase, ToUpperCamelCase};
+use heck::{ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
 
 pub fn sanitize_identifier(s: impl AsRef<str>) -> String {
     let ident = s.as_ref();
@@ -17,11 +17,11 @@ pub fn sanitize_identifier(s: impl AsRef<str>) -> String {
         | "abstract" | "become" | "box" | "do" | "final" | "macro" | "override" | "priv" | "typeof"
         | "unsized" | "virtual" | "yield"
         // 2018 reserved keywords.
-        | "async" | "await" | "try" => format!("r#{}", ident),
+        | "async" | "await" | "try" => format!("r#{ident}"),
         // the following keywords are not supported as raw identifiers and are therefore suffixed with an underscore.
-        "_" | "super" | "self" | "Self" | "extern" | "crate" => format!("{}_", ident),
+        "_" | "super" | "self" | "Self" | "extern" | "crate" => format!("{ident}_"),
         // the following keywords begin with a number and are therefore prefixed with an underscore.
-        s if s.starts_with(|c: char| c.is_numeric()) => format!("_{}", ident),
+        s if s.starts_with(|c: char| c.is_numeric()) => format!("_{ident}"),
         _ => ident.to_string(),
     }
 }
@@ -37,6 +37,125 @@ pub fn to_upper_camel(s: impl AsRef<str>) -> String {
     sanitize_identifier(s.as_ref().to_upper_camel_case())
 }
 
+/// Converts an identifier to a `SHOUTY_SNAKE` case Rust constant identifier.
+pub fn to_shouty_snake(s: impl AsRef<str>) -> String {
+    sanitize_identifier(s.as_ref().to_shouty_snake_case())
+}
+
+/// Words whose plural and singular forms are the same.
+const UNCOUNTABLE: &[&str] = &[
+    "data",
+    "equipment",
+    "feedback",
+    "fish",
+    "information",
+    "metadata",
+    "money",
+    "news",
+    "police",
+    "rice",
+    "series",
+    "sheep",
+    "software",
+    "species",
+    "staff",
+];
+
+/// Plurals the suffix rules get wrong, with their singular.
+const IRREGULAR: &[(&str, &str)] = &[
+    ("aliases", "alias"),
+    ("analyses", "analysis"),
+    ("avalanches", "avalanche"),
+    ("bonuses", "bonus"),
+    ("buses", "bus"),
+    ("caches", "cache"),
+    ("campuses", "campus"),
+    ("canoes", "canoe"),
+    ("censuses", "census"),
+    ("children", "child"),
+    ("cookies", "cookie"),
+    ("crises", "crisis"),
+    ("criteria", "criterion"),
+    ("diagnoses", "diagnosis"),
+    ("feet", "foot"),
+    ("geese", "goose"),
+    ("halves", "half"),
+    ("headaches", "headache"),
+    ("indices", "index"),
+    ("knives", "knife"),
+    ("leaves", "leaf"),
+    ("lives", "life"),
+    ("matrices", "matrix"),
+    ("men", "man"),
+    ("mice", "mouse"),
+    ("movies", "movie"),
+    ("niches", "niche"),
+    ("oxen", "ox"),
+    ("people", "person"),
+    ("quizzes", "quiz"),
+    ("shelves", "shelf"),
+    ("shoes", "shoe"),
+    ("statuses", "status"),
+    ("syntheses", "synthesis"),
+    ("teeth", "tooth"),
+    ("theses", "thesis"),
+    ("thieves", "thief"),
+    ("toes", "toe"),
+    ("vertices", "vertex"),
+    ("viruses", "virus"),
+    ("wives", "wife"),
+    ("wolves", "wolf"),
+    ("women", "woman"),
+    ("zombies", "zombie"),
+];
+
+/// Converts a plural `snake_case` table name to its singular form, e.g. `authors` to `author`.
+///
+/// Only the last word is inflected, so `river_jobs` becomes `river_job`. Uncountable and irregular
+/// words come from fixed lists, other words follow the common English suffixes and names that
+/// already look singular are returned unchanged.
+pub fn singularize(s: &str) -> String {
+    let (prefix, word) = s.rsplit_once('_').unwrap_or(("", s));
+    let singular = singularize_word(word);
+    if prefix.is_empty() {
+        singular
+    } else {
+        format!("{prefix}_{singular}")
+    }
+}
+
+fn singularize_word(word: &str) -> String {
+    if UNCOUNTABLE.contains(&word) {
+        return word.to_string();
+    }
+    if let Some((_, singular)) = IRREGULAR.iter().find(|(plural, _)| *plural == word) {
+        return (*singular).to_string();
+    }
+    if let Some(stem) = word.strip_suffix("ies") {
+        // `ties` and `pies` keep their `ie`.
+        return if stem.len() > 1 {
+            format!("{stem}y")
+        } else {
+            format!("{stem}ie")
+        };
+    }
+    for suffix in ["sses", "shes", "ches", "xes", "zzes"] {
+        if word.ends_with(suffix) {
+            return word[..word.len() - 2].to_string();
+        }
+    }
+    if ["ss", "us", "is"]
+        .iter()
+        .any(|suffix| word.ends_with(suffix))
+    {
+        return word.to_string();
+    }
+    word.strip_suffix('s')
+        .filter(|stem| !stem.is_empty())
+        .unwrap_or(word)
+        .to_string()
+}
+
 /// Strip an enum's type name from the prefix of an enum value.
 ///
 /// This function assumes that both have been formatted to Rust's
@@ -52,12 +171,7 @@ pub fn strip_enum_prefix(prefix: &str, name: &str) -> String {
     // If the next character after the stripped prefix is not
     // uppercase, then it means that we didn't have a true prefix -
     // for example, "Foo" should not be stripped from "Foobar".
-    let stripped = if stripped
-        .chars()
-        .next()
-        .map(char::is_uppercase)
-        .unwrap_or(false)
-    {
+    let stripped = if stripped.chars().next().is_some_and(char::is_uppercase) {
         stripped
     } else {
         name
@@ -242,6 +356,42 @@ mod tests {
         assert_eq!("Self_", &to_upper_camel("self"));
     }
 
+    #[test]
+    fn test_to_shouty_snake() {
+        assert_eq!("GET_AUTHOR", &to_shouty_snake("GetAuthor"));
+        assert_eq!("GET_AUTHOR", &to_shouty_snake("get_author"));
+        assert_eq!("JOB_GET_BY_ID", &to_shouty_snake("JobGetByID"));
+        assert_eq!("XML_HTTP_REQUEST", &to_shouty_snake("XMLHttpRequest"));
+        assert_eq!("_1ST_JOB", &to_shouty_snake("1stJob"));
+    }
+
+    #[test]
+    fn test_singularize() {
+        assert_eq!("author", &singularize("authors"));
+        assert_eq!("river_job", &singularize("river_jobs"));
+        assert_eq!("category", &singularize("categories"));
+        assert_eq!("address", &singularize("addresses"));
+        assert_eq!("box", &singularize("boxes"));
+        assert_eq!("batch", &singularize("batches"));
+        assert_eq!("status", &singularize("status"));
+        assert_eq!("analysis", &singularize("analysis"));
+        assert_eq!("river_job", &singularize("river_job"));
+        assert_eq!("s", &singularize("s"));
+        assert_eq!("size", &singularize("sizes"));
+        assert_eq!("cache", &singularize("caches"));
+        assert_eq!("movie", &singularize("movies"));
+        assert_eq!("series", &singularize("series"));
+        assert_eq!("status", &singularize("statuses"));
+        assert_eq!("bus", &singularize("buses"));
+        assert_eq!("user_status", &singularize("user_statuses"));
+        assert_eq!("person", &singularize("people"));
+        assert_eq!("quiz", &singularize("quizzes"));
+        assert_eq!("buzz", &singularize("buzzes"));
+        assert_eq!("tie", &singularize("ties"));
+        assert_eq!("metadata", &singularize("metadata"));
+        assert_eq!("house", &singularize("houses"));
+    }
+
     #[test]
     fn test_strip_enum_prefix() {
         assert_eq!(strip_enum_prefix("Foo", "FooBar"), "Bar");
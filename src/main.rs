@@ -11,14 +11,25 @@ mod ident;
 
 // Include the `items` module, which is generated from items.proto.
 // It is important to maintain the same structure as in the proto.
+#[allow(clippy::struct_excessive_bools)]
 mod plugin {
     include!(concat!(env!("OUT_DIR"), "/plugin.rs"));
 }
 
 #[derive(serde::Serialize, serde::Deserialize, Debug)]
 struct PluginOption {
-    pub driver: String,
+    pub driver: codegen::Driver,
     pub debug: bool,
+    #[serde(default)]
+    pub overrides: Vec<codegen::Override>,
+    #[serde(default)]
+    pub rename: codegen::Rename,
+    #[serde(default)]
+    pub composite_types: Vec<codegen::CompositeType>,
+    #[serde(flatten)]
+    pub types: codegen::TypeOptions,
+    #[serde(flatten)]
+    pub output: codegen::Output,
 }
 
 fn deserialize_codegen_request(buf: &[u8]) -> Result<plugin::GenerateRequest, prost::DecodeError> {
@@ -53,14 +64,26 @@ fn process_request() -> Result<(), Box<dyn Error>> {
     let req = deserialize_codegen_request(buffer.as_slice())?;
 
     let plugin_option: PluginOption = serde_json::from_slice(req.plugin_options.as_slice())?;
+    let engine: codegen::Engine = req
+        .settings
+        .as_ref()
+        .map_or("", |settings| settings.engine.as_str())
+        .parse()?;
 
     let mut gen = codegen::Generator {
         req: req.clone(),
+        driver: plugin_option.driver,
+        engine,
+        overrides: plugin_option.overrides,
+        rename: plugin_option.rename,
+        composite_types: plugin_option.composite_types,
+        types: plugin_option.types,
+        output: plugin_option.output,
         structs: elsa::vec::FrozenVec::new(),
     };
 
     let mut resp = plugin::GenerateResponse {
-        files: gen.generate(),
+        files: gen.generate()?,
     };
 
     if plugin_option.debug {
@@ -82,3 +105,23 @@ fn main() {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plugin_option_driver() {
+        let option: PluginOption =
+            serde_json::from_str(r#"{"driver":"tokio-postgres","debug":false}"#)
+                .expect("tokio-postgres is a driver");
+        assert_eq!(option.driver, codegen::Driver::TokioPostgres);
+
+        let err = serde_json::from_str::<PluginOption>(r#"{"driver":"diesel","debug":false}"#)
+            .expect_err("diesel is not a driver");
+        assert!(
+            err.to_string().contains("unknown variant `diesel`"),
+            "{err}"
+        );
+    }
+}